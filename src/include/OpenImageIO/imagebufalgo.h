@@ -442,6 +442,32 @@ bool OIIO_API channels (ImageBuf &dst, const ImageBuf &src,
                         bool shuffle_channel_names=false, int nthreads=0);
 
 
+/// Channel selection by name: return (or store in `dst`) a copy of `src`
+/// with just the channels named in `channelnames`, in that order, and with
+/// those names. This is a convenience wrapper around `channels()` that is
+/// robust to the channel ordering of `src`.
+///
+/// @param  channelnames
+///             The names of the `src` channels to copy, one for each
+///             channel of the result.
+/// @param  fill_missing
+///             If true, any requested channel that is not present in `src`
+///             is filled with the constant `fillvalue` (and still given the
+///             requested name). If false (the default), a requested channel
+///             that is not present in `src` is an error.
+/// @param  fillvalue
+///             The value for missing channels when `fill_missing` is true.
+ImageBuf OIIO_API channels_by_name (const ImageBuf &src,
+                                    cspan<std::string> channelnames,
+                                    bool fill_missing=false,
+                                    float fillvalue=0.0f, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API channels_by_name (ImageBuf &dst, const ImageBuf &src,
+                                cspan<std::string> channelnames,
+                                bool fill_missing=false,
+                                float fillvalue=0.0f, int nthreads=0);
+
+
 /// Append the channels of `A` and `B` together into `dst` over the region
 /// of interest.  If the region passed is uninitialized (the default), it
 /// will be interpreted as being the union of the pixel windows of `A` and `B`
@@ -554,6 +580,22 @@ bool OIIO_API transpose (ImageBuf &dst, const ImageBuf &src,
 /// @}
 
 
+/// Return (or copy into `dst`) a subregion of `src`, mirrored within the
+/// display/full window along any combination of the x, y, and z axes.
+/// Flipping along y alone is equivalent to `flip()`, along x alone is
+/// equivalent to `flop()`, and along both x and y is equivalent to
+/// `rotate180()`; flipping along z reverses the order of the slices of a
+/// volume image. If none of the axes are flipped, this is simply a copy.
+/// Unlike chaining the individual operations, all the requested axes are
+/// mirrored in a single pass over the pixels.
+ImageBuf OIIO_API flip_axes (const ImageBuf &src, bool flip_x, bool flip_y,
+                             bool flip_z=false, ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API flip_axes (ImageBuf &dst, const ImageBuf &src, bool flip_x,
+                         bool flip_y, bool flip_z=false, ROI roi={},
+                         int nthreads=0);
+
+
 /// Return (or store into `dst`) a copy of `src`, but with whatever seties
 /// of rotations, flips, or flops are necessary to transform the pixels into
 /// the configuration suggested by the "Orientation" metadata of the image
@@ -776,6 +818,57 @@ bool OIIO_API fit(ImageBuf &dst, const ImageBuf &src, KWArgs options = {},
 /// @}
 
 
+/// Return `src` resized by a factor of `scale` in each direction (both its
+/// data and display windows are scaled, rounding to whole pixels), using
+/// the named reconstruction filter as `resize()` would.
+///
+/// Sharp filters such as "lanczos3" overshoot on either side of a
+/// high-contrast edge, leaving visible halos ("ringing"). If `deringing` is
+/// true, each result pixel is then clamped to the range of the 2x2 `src`
+/// pixels nearest to where it samples `src`, which removes the overshoot
+/// while keeping the sharpness of the filter elsewhere.
+///
+/// If `dst` is already initialized, `scale` is ignored and `src` is
+/// resized to fit `dst`'s display window, as with `resize()`.
+ImageBuf OIIO_API upscale (const ImageBuf &src, float scale,
+                           string_view filtername = "lanczos3",
+                           bool deringing = true,
+                           ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API upscale (ImageBuf &dst, const ImageBuf &src, float scale,
+                       string_view filtername = "lanczos3",
+                       bool deringing = true,
+                       ROI roi={}, int nthreads=0);
+
+
+/// @defgroup seam_carve (seam_carve: content-aware shrinking)
+/// @{
+///
+/// Shrink the region of `src` given by `roi` to `width` x `height` pixels
+/// by "seam carving": repeatedly removing the connected path of pixels
+/// (one per row, or one per column) with the least total energy, so that
+/// regions with strong detail are preserved while smooth areas absorb the
+/// change in size. The energy of each pixel is the sum, over all channels,
+/// of the magnitude of its Sobel gradient. Columns are removed first, then
+/// rows.
+///
+/// The result has the same data format and channels as `src`, a data
+/// window with its origin at the corner of `roi`, and a matching display
+/// window. It is an error for `width` or `height` to be less than 1 or
+/// larger than the corresponding dimension of `roi`, or for `src` to be a
+/// deep or volume image.
+///
+/// Each seam requires recomputing the energy of the remaining pixels, so
+/// the cost grows with the product of the image size and the number of
+/// seams removed.
+
+ImageBuf OIIO_API seam_carve (const ImageBuf &src, int width, int height,
+                              ROI roi={}, int nthreads=0);
+bool OIIO_API seam_carve (ImageBuf &dst, const ImageBuf &src,
+                          int width, int height, ROI roi={}, int nthreads=0);
+/// @}
+
+
 /// @defgroup warp (warp: arbitrary warp by a 3x3 matrix)
 /// @{
 ///
@@ -1041,6 +1134,30 @@ bool OIIO_API zover (ImageBuf &dst, const ImageBuf &A, const ImageBuf &B,
                      bool z_zeroisinf=false, ROI roi={}, int nthreads=0);
 
 
+/// Return an RGBA image that pulls a matte from `src` by chroma keying:
+/// the alpha of each pixel is based on the Euclidean distance between its
+/// RGB (the first three channels) and `keycolor`. Pixels within
+/// `tolerance` of the key color get alpha 0, pixels farther away than
+/// `tolerance + softness` get alpha 1, and the alpha ramps smoothly in
+/// between. If `src` has an alpha channel, the matte is multiplied by it.
+///
+/// If `despill` is true, the channel in which the key color is strongest
+/// is limited to the larger of the other two color channels, removing the
+/// color cast that a green (or blue) screen spills onto the foreground.
+/// The resulting color is premultiplied by the new alpha, so the result
+/// can be composited directly with `over()`.
+ImageBuf OIIO_API chroma_key (const ImageBuf &src, cspan<float> keycolor,
+                              float tolerance = 0.1f, float softness = 0.1f,
+                              bool despill = true,
+                              ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API chroma_key (ImageBuf &dst, const ImageBuf &src,
+                          cspan<float> keycolor,
+                          float tolerance = 0.1f, float softness = 0.1f,
+                          bool despill = true,
+                          ROI roi={}, int nthreads=0);
+
+
 
 /// Compute per-pixel value inverse `1.0 - A` (which you can think of as
 /// roughly meaning switching white and black), returning the result image.
@@ -1234,6 +1351,29 @@ OIIO_API bool contrast_remap (ImageBuf &dst, const ImageBuf &src,
                     ROI={}, int nthreads=0);
 
 
+/// Return pixel values that are remapped through a smooth curve passing
+/// through a set of control points, as one would use for curve-based
+/// grading. The `points` span holds the control points as consecutive
+/// (input, output) pairs, i.e., `{x0, y0, x1, y1, ...}`, with at least two
+/// points and the input values strictly increasing.
+///
+/// The curve is a monotone cubic (Fritsch-Carlson) interpolant, so it
+/// passes exactly through every control point and never overshoots: it is
+/// increasing wherever the control points are increasing. Inputs below the
+/// first control point or above the last one are clamped to the first or
+/// last output value.
+///
+/// If `channel` is -1, the curve is applied to all channels in the ROI;
+/// otherwise it is applied only to that channel, and the other channels
+/// are copied unaltered.
+ImageBuf OIIO_API curve_remap (const ImageBuf &src, cspan<float> points,
+                               int channel = -1, ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API curve_remap (ImageBuf &dst, const ImageBuf &src,
+                           cspan<float> points, int channel = -1,
+                           ROI roi={}, int nthreads=0);
+
+
 /// @defgroup saturate (Adjust saturation of color channels)
 /// @{
 ///
@@ -1346,6 +1486,125 @@ bool OIIO_API rangeexpand (ImageBuf &dst, const ImageBuf &src,
 /// @}
 
 
+/// @defgroup tonemap (Tone mapping of high dynamic range images)
+/// @{
+///
+/// Tone mapping of high dynamic range images
+///
+/// Return (or copy into `dst`) the pixels of `src` within the ROI, with the
+/// color channels compressed from an unbounded high dynamic range into the
+/// [0,1] range suitable for display. Alpha and z channels are copied
+/// unaltered. Before the tone curve is applied, each color value is scaled
+/// by `2^exposure` (i.e., `exposure` is expressed in stops), and negative
+/// values are clamped to zero.
+///
+/// The `method` selects the tone curve, applied independently to each
+/// color channel:
+///
+///   - "reinhard" (the default): the simple Reinhard operator
+///     `x / (1 + x)`.
+///   - "reinhard_extended": the extended Reinhard operator
+///     `x * (1 + x/w^2) / (1 + x)`, which maps the value `w` given by
+///     `whitepoint` to 1.0 (and thus allows the brightest values to
+///     reach full white). If `whitepoint` <= 0, the maximum color value
+///     of `src` within the ROI (after exposure scaling) is used.
+///   - "aces": Krzysztof Narkowicz's curve-fit approximation of the ACES
+///     filmic tone curve, clamped to [0,1].
+
+ImageBuf OIIO_API tonemap (const ImageBuf &src, string_view method = "reinhard",
+                           float exposure = 0.0f, float whitepoint = 0.0f,
+                           ROI roi={}, int nthreads=0);
+bool OIIO_API tonemap (ImageBuf &dst, const ImageBuf &src,
+                       string_view method = "reinhard", float exposure = 0.0f,
+                       float whitepoint = 0.0f, ROI roi={}, int nthreads=0);
+/// @}
+
+
+/// @defgroup white_balance (White balance adjustment)
+/// @{
+///
+/// White balance adjustment
+///
+/// Return (or copy into `dst`) the pixels of `src` within the ROI, with the
+/// first three (presumed to be linear Rec709 RGB) channels scaled by
+/// per-channel gains that shift the white point along the blackbody
+/// (Planckian) locus. Any other channels are copied unaltered.
+///
+/// The gains are the ratio of the RGB color of a blackbody at
+/// `temperature` (in Kelvin, clamped to [2000, 25000]) to that of a 6500K
+/// blackbody, so 6500 leaves the colors unchanged, lower temperatures
+/// give a warmer (redder) image, and higher temperatures a cooler (bluer)
+/// one. The green gain is further multiplied by `2^(-tint)`, so positive
+/// `tint` shifts toward magenta and negative `tint` toward green. Finally,
+/// the gains are normalized so that the luminance of a neutral color is
+/// preserved.
+///
+/// It is an error for `src` to have fewer than three channels.
+
+ImageBuf OIIO_API white_balance (const ImageBuf &src, float temperature = 6500.0f,
+                                 float tint = 0.0f, ROI roi={}, int nthreads=0);
+bool OIIO_API white_balance (ImageBuf &dst, const ImageBuf &src,
+                             float temperature = 6500.0f, float tint = 0.0f,
+                             ROI roi={}, int nthreads=0);
+/// @}
+
+
+/// @defgroup vignette (Vignette / radial falloff)
+/// @{
+///
+/// Vignette / radial falloff
+///
+/// Return (or copy into `dst`) the pixels of `src` within the ROI,
+/// darkened toward the edges by a radial falloff centered on the display
+/// (full) window. Alpha and z channels are copied unaltered.
+///
+/// Distance from the center is normalized so that the corners of the
+/// display window are at distance 1. Pixels closer than `radius` are
+/// unchanged, and beyond it the color channels are scaled by a factor
+/// that falls smoothly to `1 - amount` at the corners (and stays there
+/// for any pixels farther out). Thus `amount = 0` leaves the image
+/// unchanged and `amount = 1` fades the corners to black.
+///
+/// The `roundness` controls the shape of the falloff: 0 makes it an
+/// ellipse with the aspect ratio of the display window, 1 makes it
+/// circular, and values in between blend the two.
+
+ImageBuf OIIO_API vignette (const ImageBuf &src, float amount = 0.5f,
+                            float radius = 0.5f, float roundness = 1.0f,
+                            ROI roi={}, int nthreads=0);
+bool OIIO_API vignette (ImageBuf &dst, const ImageBuf &src,
+                        float amount = 0.5f, float radius = 0.5f,
+                        float roundness = 1.0f, ROI roi={}, int nthreads=0);
+/// @}
+
+
+/// @defgroup film_grain (Simulated film grain)
+/// @{
+///
+/// Simulated film grain
+///
+/// Return (or copy into `dst`) the pixels of `src` within the ROI, with
+/// simulated film grain added to the color channels. The grain is
+/// Gaussian noise blurred by a Gaussian kernel `size` pixels wide (so
+/// larger sizes give coarser, clumpier grain; 0 gives independent
+/// per-pixel noise), then scaled so that its standard deviation is
+/// `intensity`. Alpha and z channels are copied unaltered.
+///
+/// If `mono` is true, the same grain is added to every color channel;
+/// otherwise each channel gets its own. As with `noise()`, the grain
+/// pattern depends only on the pixel coordinates and `seed`, so a given
+/// seed always produces the same result.
+
+ImageBuf OIIO_API film_grain (const ImageBuf &src, float intensity = 0.05f,
+                              float size = 1.5f, int seed = 0,
+                              bool mono = true, ROI roi={}, int nthreads=0);
+bool OIIO_API film_grain (ImageBuf &dst, const ImageBuf &src,
+                          float intensity = 0.05f, float size = 1.5f,
+                          int seed = 0, bool mono = true,
+                          ROI roi={}, int nthreads=0);
+/// @}
+
+
 struct OIIO_API PixelStats {
     std::vector<float> min;
     std::vector<float> max;
@@ -1372,6 +1631,18 @@ struct OIIO_API PixelStats {
 PixelStats OIIO_API computePixelStats (const ImageBuf &src,
                                        ROI roi={}, int nthreads=0);
 
+/// Compute statistics about the ROI of the given subimage and MIP level of
+/// an open ImageInput, reading the pixels in bands of scanlines (or rows of
+/// tiles, for tiled files) so that the whole image never needs to be held
+/// in memory at once. The results are
+/// the same as those of the ImageBuf version of `computePixelStats()` for
+/// the same pixels, but this makes it practical to analyze images too large
+/// to fit in RAM. If there is a failure, the vector sizes will be 0 and an
+/// error will be set in `in`. Deep images are not supported.
+PixelStats OIIO_API computePixelStats (ImageInput &in, int subimage=0,
+                                       int miplevel=0, ROI roi={},
+                                       int nthreads=0);
+
 // DEPRECATED(1.9): with C++11 move semantics, there's no reason why
 // stats needs to be passed as a parameter instead of returned.
 #ifndef DOXYGEN_SHOULD_SKIP_THIS
@@ -1426,6 +1697,16 @@ CompareResults OIIO_API compare (const ImageBuf &A, const ImageBuf &B,
                                  float failthresh, float warnthresh,
                                  ROI roi={}, int nthreads=0);
 
+/// Return true if images `A` and `B` have the same content: the same data
+/// window and number of channels, and pixel values that differ by no more
+/// than `tolerance` in any channel (as measured by `compare()`). The data
+/// formats of the two images need not match. If `compare_metadata` is
+/// true, the channel names and all of the named metadata must also be
+/// identical (in any order); otherwise, metadata differences are ignored.
+bool OIIO_API content_equals (const ImageBuf &A, const ImageBuf &B,
+                              bool compare_metadata = false,
+                              float tolerance = 0.0f, int nthreads=0);
+
 /// Compare two images using Hector Yee's perceptual metric, returning
 /// the number of pixels that fail the comparison.  Only the first three
 /// channels (or first three channels specified by `roi`) are compared.
@@ -1561,6 +1842,56 @@ bool OIIO_API color_range_check (const ImageBuf &src,
 OIIO_API ROI nonzero_region (const ImageBuf &src, ROI roi={}, int nthreads=0);
 
 
+/// Label the connected regions of "foreground" pixels of `src` within the
+/// ROI, where a pixel is foreground if its value in the given `channel` is
+/// greater than `threshold`. The result is a single-channel float image
+/// named "label" covering the ROI, in which background pixels are 0 and
+/// each connected region of foreground pixels has a distinct label 1, 2,
+/// ... N, numbered in scanline order of each region's first pixel (so the
+/// number of regions is the maximum label value).
+///
+/// The `connectivity` may be 4 (pixels touching along an edge are
+/// connected) or 8 (diagonal neighbors are also connected). Only 2D images
+/// are supported. The labeling is a sequential pass over the image, so it
+/// is always single-threaded and `nthreads` is ignored.
+ImageBuf OIIO_API connected_components (const ImageBuf &src, int channel = 0,
+                                        float threshold = 0.5f,
+                                        int connectivity = 8,
+                                        ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized),
+/// and if `ncomponents` is not null, store the number of regions found.
+bool OIIO_API connected_components (ImageBuf &dst, const ImageBuf &src,
+                                    int channel = 0, float threshold = 0.5f,
+                                    int connectivity = 8,
+                                    int *ncomponents = nullptr,
+                                    ROI roi={}, int nthreads=0);
+
+
+/// Estimate the translation that best aligns `target` with `reference`,
+/// that is, the offset `(dx, dy)` for which `target(x, y)` best matches
+/// `reference(x - dx, y - dy)`. This is useful for registering bracketed
+/// exposures or stabilizing frames before combining them.
+///
+/// Offsets of up to `max_shift` pixels in each direction are considered,
+/// comparing the mean squared difference of all channels of the ROI
+/// (which defaults to the overlap of the two data windows) over the area
+/// where the shifted images overlap; `max_shift` is limited to half the
+/// ROI size so that the overlap is never less than a quarter of it. The
+/// search is coarse-to-fine: every offset in range is tried on a reduced
+/// resolution copy of the images, and the best one is then refined by
+/// searching +/- 2 pixels at each successively finer level. The best
+/// integer offset is then refined to sub-pixel precision by fitting a
+/// parabola through the neighboring errors. Only 2D images are supported.
+///
+/// Return true upon success, storing the offset in `dx` and `dy`. If there
+/// was an error, return false, and an error message will be retrievable
+/// from reference.geterror().
+bool OIIO_API estimate_translation (const ImageBuf &reference,
+                                    const ImageBuf &target,
+                                    float &dx, float &dy, int max_shift = 16,
+                                    ROI roi={}, int nthreads=0);
+
+
 /// Compute the SHA-1 byte hash for all the pixels in the specified region of
 /// the image.  If `blocksize` > 0, the function will compute separate SHA-1
 /// hashes of each `blocksize` batch of scanlines, then return a hash of the
@@ -1592,6 +1923,37 @@ std::vector<imagesize_t> histogram (const ImageBuf &src, int channel=0,
                                     ROI roi={}, int nthreads=0);
 
 
+/// Compute the values at the requested percentiles of each channel of
+/// `src` within the ROI. Each entry of `percentiles` is in the range
+/// [0,100], where 0 gives the minimum value, 50 the median, and 100 the
+/// maximum; results between two pixel values are linearly interpolated.
+/// NaN values are ignored. The result has one entry per channel in the
+/// ROI, each of which is a vector of the same length as `percentiles`.
+///
+/// If there was an error, the returned vector will be empty, and an error
+/// message will be retrievable from src.geterror().
+OIIO_API std::vector<std::vector<float>>
+percentiles (const ImageBuf &src, cspan<float> percentiles,
+             ROI roi={}, int nthreads=0);
+
+
+/// Find the `k` dominant colors of `src` within the ROI by k-means
+/// clustering of its pixel values (over all channels of the ROI). The
+/// result is a vector of up to `k` colors, each a vector with one value
+/// per channel in the ROI, sorted from the most to the least populous
+/// cluster. Fewer than `k` colors are returned if the ROI does not contain
+/// that many distinct pixel values. Pixels with any NaN value are ignored.
+///
+/// The clustering is deterministic: the initial centers are picked by
+/// starting from the pixel closest to the mean and repeatedly adding the
+/// pixel farthest from all centers chosen so far.
+///
+/// If there was an error, the returned vector will be empty, and an error
+/// message will be retrievable from src.geterror().
+OIIO_API std::vector<std::vector<float>>
+palette (const ImageBuf &src, int k, ROI roi={}, int nthreads=0);
+
+
 #ifndef DOXYGEN_SHOULD_SKIP_THIS
 /// DEPRECATED(1.9)
 OIIO_DEPRECATED("use version that returns vector (1.9)")
@@ -1620,7 +1982,7 @@ bool OIIO_API histogram_draw (ImageBuf &dst,
 ///
 /// Kernel names can be: "gaussian", "sharp-gaussian", "box",
 /// "triangle", "blackman-harris", "mitchell", "b-spline", "catmull-rom",
-/// "lanczos3", "disk", "binomial", "laplacian".
+/// "lanczos3", "disk", "binomial", "laplacian", "sobel-x", "sobel-y".
 ///
 /// Note that "catmull-rom" and "lanczos3" are fixed-size kernels that
 /// don't scale with the width, and are therefore probably less useful
@@ -1665,6 +2027,34 @@ bool OIIO_API laplacian (ImageBuf &dst, const ImageBuf &src,
                          ROI roi={}, int nthreads=0);
 
 
+/// Compute the Sobel gradients of the corresponding region of `src`,
+/// storing the horizontal derivative (positive where values increase
+/// toward +x) in `dst_x` and the vertical derivative (positive where values
+/// increase toward +y, i.e., downward) in `dst_y`. They are approximated
+/// by convolving the image with the discrete 3x3 Sobel kernels,
+///
+///              x:  [ -1  0  1 ]          y:  [ -1 -2 -1 ]
+///                  [ -2  0  2 ]              [  0  0  0 ]
+///                  [ -1  0  1 ]              [  1  2  1 ]
+///
+/// Since the gradients may be negative, `dst_x` and `dst_y` will be float
+/// images if they were not already initialized.
+bool OIIO_API sobel (ImageBuf &dst_x, ImageBuf &dst_y, const ImageBuf &src,
+                     ROI roi={}, int nthreads=0);
+
+
+/// Return the magnitude of the Sobel gradient of the corresponding region
+/// of `src`, that is, `sqrt(Gx^2 + Gy^2)` where `Gx` and `Gy` are the
+/// horizontal and vertical gradients computed by `sobel()`. This is a
+/// simple edge detector. Since the magnitude may exceed 1, the result will
+/// be a float image if `dst` was not already initialized.
+ImageBuf OIIO_API gradient_magnitude (const ImageBuf &src, ROI roi={},
+                                      int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API gradient_magnitude (ImageBuf &dst, const ImageBuf &src,
+                                  ROI roi={}, int nthreads=0);
+
+
 /// @defgroup fft-ifft (Fast Fourier Transform and inverse)
 /// @{
 ///
@@ -1788,6 +2178,63 @@ bool OIIO_API fillholes_pushpull (ImageBuf &dst, const ImageBuf &src,
                                   ROI roi={}, int nthreads=0);
 
 
+/// Return a copy of `background` (within the ROI) into which the region
+/// of `src` selected by `mask` has been seamlessly cloned using
+/// gradient-domain ("Poisson") blending. Rather than copying the `src`
+/// pixel values, the cloned region is solved so that its gradients match
+/// those of `src` while its border matches the surrounding `background`,
+/// so the pasted content takes on the local color and brightness of its
+/// new surroundings without a visible seam.
+///
+/// The `mask` is in the pixel coordinates of `src`, and selects the
+/// pixels where its first channel is greater than 0.5; source pixel
+/// (x,y) is cloned to location (x + `xoffset`, y + `yoffset`) of the
+/// result. The solution is computed with `iterations` sweeps of
+/// successive over-relaxation, which is plenty for clone regions up to a
+/// few hundred pixels across. Channels beyond those present in `src` are
+/// copied from `background` unaltered.
+ImageBuf OIIO_API poisson_clone (const ImageBuf &background,
+                                 const ImageBuf &src, const ImageBuf &mask,
+                                 int xoffset = 0, int yoffset = 0,
+                                 int iterations = 500,
+                                 ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API poisson_clone (ImageBuf &dst, const ImageBuf &background,
+                             const ImageBuf &src, const ImageBuf &mask,
+                             int xoffset = 0, int yoffset = 0,
+                             int iterations = 500,
+                             ROI roi={}, int nthreads=0);
+
+
+/// Return the exposure fusion of a set of bracketed `images` of the same
+/// scene, using the method of Mertens, Kautz, and Van Reeth (2007). Each
+/// pixel of each input is weighted by the product of its local contrast
+/// (absolute value of the Laplacian of the channel average), saturation
+/// (standard deviation across the color channels), and well-exposedness
+/// (closeness of every color channel to 0.5), each raised to the power
+/// `contrast_weight`, `saturation_weight`, and `exposure_weight`,
+/// respectively. A weight of 0 disables that measure. The inputs are then
+/// blended with those normalized weights at every level of a Laplacian
+/// pyramid, which avoids the seams that a per-pixel blend would produce,
+/// and the result is a low dynamic range image with no HDR intermediate.
+///
+/// All of the `images` must have the same data window and number of
+/// channels, and pixel values are expected to be in the nominal [0,1]
+/// range. An alpha channel, if present, is blended like any other channel
+/// but does not contribute to the weights. Only 2D images are supported.
+ImageBuf OIIO_API exposure_fusion (cspan<ImageBuf> images,
+                                   float contrast_weight = 1.0f,
+                                   float saturation_weight = 1.0f,
+                                   float exposure_weight = 1.0f,
+                                   ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API exposure_fusion (ImageBuf &dst, cspan<ImageBuf> images,
+                               float contrast_weight = 1.0f,
+                               float saturation_weight = 1.0f,
+                               float exposure_weight = 1.0f,
+                               ROI roi={}, int nthreads=0);
+
+
 /// Return a median-filtered version of the corresponding region of `src`.
 /// The median filter replaces each pixel with the median value underneath
 /// the `width` x `height` window surrounding it. If `height` <= 0, it will
@@ -1805,6 +2252,58 @@ bool OIIO_API median_filter (ImageBuf &dst, const ImageBuf &src,
                              ROI roi={}, int nthreads=0);
 
 
+/// Return a bilateral-filtered version of the corresponding region of
+/// `src`. Each pixel is replaced by a weighted average of its neighbors,
+/// where the weight is the product of a Gaussian of the spatial distance
+/// (with standard deviation `sigma_spatial`, in pixels) and a Gaussian of
+/// the color difference from the center pixel (with standard deviation
+/// `sigma_range`, in pixel value units). The window extends out to
+/// `ceil(2*sigma_spatial)` pixels in each direction.
+///
+/// The color difference is the Euclidean distance over all channels of
+/// the ROI except alpha and z, and all channels (including alpha) are
+/// averaged with the same weights. Bilateral filters smooth noise in flat
+/// regions while preserving edges whose contrast is large compared to
+/// `sigma_range`.
+ImageBuf OIIO_API bilateral_filter (const ImageBuf &src,
+                                    float sigma_spatial = 1.0f,
+                                    float sigma_range = 0.1f,
+                                    ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API bilateral_filter (ImageBuf &dst, const ImageBuf &src,
+                                float sigma_spatial = 1.0f,
+                                float sigma_range = 0.1f,
+                                ROI roi={}, int nthreads=0);
+
+
+/// Return a joint bilateral upsampling of `src` (for example, a low
+/// resolution mask or AOV) to the resolution of the higher resolution
+/// `guide` image. Each result pixel is a weighted average of the nearby
+/// pixels of `src`, where the weight is the product of a Gaussian of the
+/// spatial distance (with standard deviation `sigma_spatial`, measured in
+/// `src` pixels) and a Gaussian of the difference between the `guide`
+/// color at the result pixel and the `guide` color at the center of the
+/// `src` pixel (with standard deviation `sigma_range`). This lets edges in
+/// the result follow the edges of `guide` rather than the blocky edges of
+/// `src`.
+///
+/// The result has the data and display windows of `guide` and the
+/// channels of `src`. The two images are related by their display windows.
+/// The color difference is the Euclidean distance over all channels of
+/// `guide` except alpha and z.
+ImageBuf OIIO_API joint_bilateral_upsample (const ImageBuf &src,
+                                            const ImageBuf &guide,
+                                            float sigma_spatial = 1.0f,
+                                            float sigma_range = 0.1f,
+                                            ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API joint_bilateral_upsample (ImageBuf &dst, const ImageBuf &src,
+                                        const ImageBuf &guide,
+                                        float sigma_spatial = 1.0f,
+                                        float sigma_range = 0.1f,
+                                        ROI roi={}, int nthreads=0);
+
+
 /// Return a sharpened version of the corresponding region of `src` using
 /// the "unsharp mask" technique. Unsharp masking basically works by first
 /// blurring the image (low pass filter), subtracting this from the original
@@ -1836,6 +2335,32 @@ bool OIIO_API unsharp_mask (ImageBuf &dst, const ImageBuf &src,
                             ROI roi={}, int nthreads=0);
 
 
+/// Return a version of the corresponding region of `src` with boosted
+/// local contrast (sometimes called "clarity"). Unlike `unsharp_mask()`,
+/// the large-scale base that the detail is measured against comes from an
+/// edge-preserving bilateral filter rather than a plain blur, so strong
+/// edges are not exaggerated and do not grow halos. Roughly speaking,
+///
+///      dst = src + amount * (src - bilateral_filter(src, radius, sigma_range))
+///
+/// The `radius` is the spatial standard deviation of the filter, in
+/// pixels, and controls the scale of the detail that is enhanced. The
+/// `sigma_range` is the range (pixel value) standard deviation of the
+/// filter: edges with a contrast much larger than `sigma_range` are
+/// protected. The default of 0.1 suits display-referred images with values
+/// in [0,1]; for HDR images, pass a value in proportion to the range of
+/// pixel values. An `amount` of 0 leaves the image unchanged, and negative
+/// amounts soften local contrast. Flat regions are left unchanged.
+ImageBuf OIIO_API clarity (const ImageBuf &src, float amount = 0.5f,
+                           float radius = 5.0f, float sigma_range = 0.1f,
+                           ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API clarity (ImageBuf &dst, const ImageBuf &src,
+                       float amount = 0.5f, float radius = 5.0f,
+                       float sigma_range = 0.1f,
+                       ROI roi={}, int nthreads=0);
+
+
 /// Return a dilated version of the corresponding region of `src`. Dilation
 /// is defined as the maximum value of all pixels under nonzero values of
 /// the structuring element (which is taken to be a width x height square).
@@ -2503,6 +3028,22 @@ ImageBuf OIIO_API flatten (const ImageBuf &src, ROI roi={}, int nthreads=0);
 bool OIIO_API flatten (ImageBuf &dst, const ImageBuf &src,
                        ROI roi={}, int nthreads=0);
 
+/// Flatten deep image `src`, as above, with optional controls over the
+/// compositing given by the following named `options`:
+///
+///   - `alpha_threshold` (float, default 1.0) : Stop compositing the
+///     samples of a pixel once its accumulated alpha reaches this value.
+///     Values less than 1 allow nearly-opaque pixels to ignore the samples
+///     behind them.
+///   - `sort` (int, default 0) : If nonzero, composite the samples of each
+///     pixel in order of increasing Z, rather than assuming that they are
+///     already stored front to back.
+ImageBuf OIIO_API flatten (const ImageBuf &src, KWArgs options,
+                           ROI roi={}, int nthreads=0);
+/// Write to an existing image `dst` (allocating if it is uninitialized).
+bool OIIO_API flatten (ImageBuf &dst, const ImageBuf &src, KWArgs options,
+                       ROI roi={}, int nthreads=0);
+
 
 /// Return the deep merge of the samples of deep images `A` and `B`,
 /// overwriting any existing samples of `dst` in the ROI. If
@@ -130,9 +130,11 @@ struct ROI {
     /// std::min (imagebuf.nchannels(), roi.nchannels()).
     constexpr int nchannels () const noexcept { return chend - chbegin; }
 
-    /// Total number of pixels in the region.
+    /// Total number of pixels in the region. An empty region (one with no
+    /// extent in some dimension, such as the intersection of two disjoint
+    /// regions) has 0 pixels.
     constexpr imagesize_t npixels () const noexcept {
-        return defined()
+        return (defined() && width() > 0 && height() > 0 && depth() > 0)
             ? imagesize_t(width()) * imagesize_t(height()) * imagesize_t(depth())
             : 0;
     }
@@ -509,6 +511,18 @@ public:
                           TypeDesc searchtype=TypeDesc::UNKNOWN,
                           bool casesensitive=false);
 
+    /// Remove from `extra_attribs` every attribute whose name matches any
+    /// of the `patterns`, which are simple (case-insensitive) globs: `*`
+    /// matches any sequence of characters and `?` any single character.
+    /// For example, `strip_attributes({"farm:*", "Artist"})` removes all
+    /// attributes in the "farm" namespace as well as "Artist".
+    void strip_attributes (cspan<std::string> patterns);
+
+    /// Remove from `extra_attribs` every attribute whose name does NOT
+    /// match any of the glob `patterns` (with the same syntax as
+    /// `strip_attributes()`), keeping only the ones that do.
+    void keep_only_attributes (cspan<std::string> patterns);
+
     /// Searches `extra_attribs` for an attribute matching `name`, returning
     /// a pointer to the attribute record, or NULL if there was no match.
     /// If `searchtype` is anything other than `TypeDesc::UNKNOWN`, matches
@@ -776,6 +790,38 @@ public:
         deep = other.deep;
     }
 
+    /// Return a copy of this `ImageSpec`, but with the pixel data window
+    /// resized to `width` x `height` (keeping its origin), and the full
+    /// (display) window resized to match. Everything else -- channel names
+    /// and formats, tiling, and all of the metadata in `extra_attribs` --
+    /// is preserved. This is handy for describing a derived image (such as
+    /// a resized copy) without losing the metadata of the original.
+    ImageSpec with_size(int width, int height) const;
+
+    /// Return a copy of this `ImageSpec`, but with the data format changed
+    /// to `fmt` (and any per-channel formats cleared, just as `set_format()`
+    /// would do). All other fields and metadata are preserved.
+    ImageSpec with_format(TypeDesc fmt) const;
+
+    /// Append the channels of each of the `others` to this spec, as when
+    /// stacking the channels of several images (such as render AOVs) into
+    /// one. The channel names are concatenated, and any name that is not
+    /// unique among all of the merged channels -- including this spec's
+    /// own -- is prefixed by the "oiio:subimagename" attribute of the spec
+    /// it came from (e.g., "diffuse.R", "specular.R"), or by "subimageN"
+    /// if that spec has no subimage name (N being its position in the
+    /// merge, with this spec being 0). If the result still isn't unique, a
+    /// number is appended to it. If the specs' data formats differ,
+    /// `channelformats` is filled in so that each channel keeps its own
+    /// type (`format` is unchanged). The first alpha or z channel found is
+    /// designated as such. Dimensions and metadata are not altered. Null
+    /// pointers in `others` are skipped.
+    void merge_channels_from(cspan<const ImageSpec*> others);
+
+    /// Append the channels of a single `other` spec to this one, just as
+    /// `merge_channels_from({&other})` would do.
+    void merge_channels_from(const ImageSpec& other);
+
     /// Set the metadata to presume that color space is `name` (or to assume
     /// nothing about the color space if `name` is empty). The core operation
     /// is to set the "oiio:ColorSpace" attribute, but it also removes or
@@ -612,6 +612,23 @@ OIIO_NAMESPACE_END
 
 
 
+namespace std {  // not necessary in C++17, then we can just say std::hash
+// std::hash specialization for TypeDesc. It uses exactly the fields that
+// TypeDesc::operator== compares, so equal types always hash equally.
+template<> struct hash<OIIO::TypeDesc> {
+    OIIO_HOSTDEVICE constexpr std::size_t
+    operator()(OIIO::TypeDesc t) const noexcept
+    {
+        return static_cast<std::size_t>(
+            uint64_t(t.basetype) | (uint64_t(t.aggregate) << 8)
+            | (uint64_t(t.vecsemantics) << 16)
+            | (uint64_t(uint32_t(t.arraylen)) << 32));
+    }
+};
+}  // namespace std
+
+
+
 // Supply a fmtlib compatible custom formatter for TypeDesc.
 #if FMT_VERSION >= 100000
 FMT_BEGIN_NAMESPACE
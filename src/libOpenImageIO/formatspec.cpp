@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // https://github.com/AcademySoftwareFoundation/OpenImageIO
 
+#include <algorithm>
 #include <cstdio>
 #include <cstdlib>
 #include <regex>
@@ -10,6 +11,7 @@
 #include <OpenImageIO/half.h>
 
 #include <OpenImageIO/dassert.h>
+#include <OpenImageIO/filesystem.h>
 #include <OpenImageIO/fmath.h>
 #include <OpenImageIO/imagebuf.h>
 #include <OpenImageIO/imageio.h>
@@ -187,6 +189,99 @@ ImageSpec::set_format(TypeDesc fmt) noexcept
 
 
 
+ImageSpec
+ImageSpec::with_size(int width, int height) const
+{
+    ImageSpec spec(*this);
+    spec.width       = width;
+    spec.height      = height;
+    spec.full_width  = width;
+    spec.full_height = height;
+    return spec;
+}
+
+
+
+ImageSpec
+ImageSpec::with_format(TypeDesc fmt) const
+{
+    ImageSpec spec(*this);
+    spec.set_format(fmt);
+    return spec;
+}
+
+
+
+void
+ImageSpec::merge_channels_from(cspan<const ImageSpec*> others)
+{
+    // Gather the channels of this spec and all the others, in order,
+    // remembering which spec each came from (0 is this one).
+    std::vector<const ImageSpec*> specs(1, this);
+    for (auto other : others)
+        if (other)
+            specs.push_back(other);
+    bool perchannel = false;
+    std::vector<std::string> bare;
+    std::vector<TypeDesc> formats;
+    std::vector<size_t> owner;
+    int alpha = -1, z = -1;
+    for (size_t i = 0; i < specs.size(); ++i) {
+        const ImageSpec& spec(*specs[i]);
+        perchannel |= !spec.channelformats.empty() || spec.format != format;
+        if (alpha < 0 && spec.alpha_channel >= 0)
+            alpha = int(bare.size()) + spec.alpha_channel;
+        if (z < 0 && spec.z_channel >= 0)
+            z = int(bare.size()) + spec.z_channel;
+        for (int c = 0; c < spec.nchannels; ++c) {
+            bare.emplace_back(spec.channel_name(c));
+            formats.push_back(spec.channelformat(c));
+            owner.push_back(i);
+        }
+    }
+
+    // Duplicate channel names will wreak havoc for OpenEXR, so every
+    // channel whose name is shared with another is prefixed by the
+    // "oiio:subimagename" of the spec it came from (or "subimageN" if that
+    // spec has none). If that still isn't unique, a number is appended.
+    std::vector<std::string> names;
+    names.reserve(bare.size());
+    for (size_t c = 0; c < bare.size(); ++c) {
+        std::string name = bare[c];
+        if (std::count(bare.begin(), bare.end(), name) > 1) {
+            std::string prefix = specs[owner[c]]->get_string_attribute(
+                "oiio:subimagename");
+            if (prefix.empty())
+                prefix = Strutil::fmt::format("subimage{}", owner[c]);
+            name = prefix + "." + name;
+        }
+        std::string unique = name;
+        for (int n = 1;
+             std::find(names.begin(), names.end(), unique) != names.end(); ++n)
+            unique = Strutil::fmt::format("{}{}", name, n);
+        names.push_back(unique);
+    }
+
+    nchannels     = int(names.size());
+    channelnames  = std::move(names);
+    alpha_channel = alpha;
+    z_channel     = z;
+    // Per-channel formats are needed if the types don't all agree
+    if (perchannel)
+        channelformats = std::move(formats);
+}
+
+
+
+void
+ImageSpec::merge_channels_from(const ImageSpec& other)
+{
+    const ImageSpec* ptr = &other;
+    merge_channels_from(cspan<const ImageSpec*>(&ptr, 1));
+}
+
+
+
 void
 ImageSpec::default_channel_names() noexcept
 {
@@ -405,6 +500,50 @@ ImageSpec::erase_attribute(string_view name, TypeDesc searchtype,
 }
 
 
+// Erase the attributes whose names match (if `matching` is true) or don't
+// match (if `matching` is false) any of the glob patterns.
+static void
+erase_attributes_by_glob(ParamValueList& attribs, cspan<std::string> patterns,
+                         bool matching)
+{
+    if (attribs.empty())
+        return;  // Don't mess with regexp if there isn't any metadata
+    try {
+        std::vector<std::regex> res;
+        for (auto&& pat : patterns)
+            res.emplace_back(Filesystem::filename_to_regex(pat),
+                             std::regex_constants::icase);
+        auto matcher = [&](const ParamValue& p) {
+            bool match = false;
+            for (auto&& re : res)
+                match |= std::regex_match(p.name().string(), re);
+            return match == matching;
+        };
+        auto del = std::remove_if(attribs.begin(), attribs.end(), matcher);
+        attribs.erase(del, attribs.end());
+    } catch (...) {
+        return;
+    }
+}
+
+
+
+void
+ImageSpec::strip_attributes(cspan<std::string> patterns)
+{
+    erase_attributes_by_glob(extra_attribs, patterns, true);
+}
+
+
+
+void
+ImageSpec::keep_only_attributes(cspan<std::string> patterns)
+{
+    erase_attributes_by_glob(extra_attribs, patterns, false);
+}
+
+
+
 ParamValue*
 ImageSpec::find_attribute(string_view name, TypeDesc searchtype,
                           bool casesensitive)
@@ -2372,6 +2372,8 @@ ImageBuf::get_pixels(ROI roi, TypeDesc format, void* result, stride_t xstride,
     if (!roi.defined())
         roi = this->roi();
     roi.chend = std::min(roi.chend, nchannels());
+    if (roi.npixels() == 0 || roi.nchannels() <= 0)
+        return true;  // Empty region: nothing to copy
     ImageSpec::auto_stride(xstride, ystride, zstride, format.size(),
                            roi.nchannels(), roi.width(), roi.height());
     if (localpixels() && this->roi().contains(roi)) {
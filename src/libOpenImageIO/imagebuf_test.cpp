@@ -357,6 +357,12 @@ test_set_get_pixels()
     float retrieved[2 * 2 * nchans] = { 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9 };
     A.get_pixels(ROI(1, 3, 1, 3, 0, 1), TypeDesc::FLOAT, retrieved);
     OIIO_CHECK_ASSERT(0 == memcmp(retrieved, newdata, 2 * 2 * nchans));
+
+    // Getting an empty region succeeds and touches nothing
+    float untouched[nchans] = { 9, 9, 9 };
+    OIIO_CHECK_ASSERT(
+        A.get_pixels(ROI(2, 2, 0, 4), TypeDesc::FLOAT, untouched));
+    OIIO_CHECK_EQUAL(untouched[0], 9.0f);
 }
 
 
@@ -459,6 +465,13 @@ test_roi()
         !buf.contains_roi(ROI(10, 640, 20, 480, 1, 2, 0, 3)));  // outside z
     OIIO_CHECK_ASSERT(
         !buf.contains_roi(ROI(10, 640, 20, 480, 0, 1, 0, 4)));  // outside ch
+
+    // Empty regions have no pixels, including the intersection of two
+    // regions that don't overlap
+    OIIO_CHECK_EQUAL(ROI(0, 0, 0, 10).npixels(), 0);
+    OIIO_CHECK_EQUAL(ROI(0, 1, 0, 1).npixels(), 1);
+    OIIO_CHECK_EQUAL(
+        roi_intersection(ROI(0, 4, 0, 4), ROI(8, 12, 0, 4)).npixels(), 0);
 }
 
 
@@ -513,6 +513,18 @@ ImageBufAlgo::make_kernel(string_view name, float width, float height,
         dst.set_pixels(dst.roi(), TypeDesc::FLOAT, vals, sizeof(float),
                        h * sizeof(float));
         normalize = false;  // sums to zero, so don't normalize it */
+    } else if (Strutil::iequals(name, "sobel-x") && w == 3 && h == 3
+               && d == 1) {
+        const float vals[9] = { -1, 0, 1, -2, 0, 2, -1, 0, 1 };
+        dst.set_pixels(dst.roi(), TypeDesc::FLOAT, vals, sizeof(float),
+                       h * sizeof(float));
+        normalize = false;  // sums to zero, so don't normalize it */
+    } else if (Strutil::iequals(name, "sobel-y") && w == 3 && h == 3
+               && d == 1) {
+        const float vals[9] = { -1, -2, -1, 0, 0, 0, 1, 2, 1 };
+        dst.set_pixels(dst.roi(), TypeDesc::FLOAT, vals, sizeof(float),
+                       h * sizeof(float));
+        normalize = false;  // sums to zero, so don't normalize it */
     } else {
         // No filter -- make a box
         float val = normalize ? 1.0f / ((w * h * d)) : 1.0f;
@@ -620,6 +632,62 @@ ImageBufAlgo::unsharp_mask(const ImageBuf& src, string_view kernel, float width,
 
 
 
+bool
+ImageBufAlgo::clarity(ImageBuf& dst, const ImageBuf& src, float amount,
+                      float radius, float sigma_range, ROI roi, int nthreads)
+{
+    // N.B. Don't log time, it will get caught by the constituent parts
+    if (!(radius > 0.0f)) {
+        dst.errorfmt("ImageBufAlgo::clarity: radius must be positive (got {})",
+                     radius);
+        return false;
+    }
+    if (!(sigma_range > 0.0f)) {
+        dst.errorfmt(
+            "ImageBufAlgo::clarity: sigma_range must be positive (got {})",
+            sigma_range);
+        return false;
+    }
+    if (!IBAprep(roi, &dst, &src,
+                 IBAprep_REQUIRE_SAME_NCHANNELS | IBAprep_NO_SUPPORT_VOLUME))
+        return false;
+
+    // Edge-preserving blur of the source image, store in Base
+    ImageSpec BaseSpec = src.spec();
+    BaseSpec.set_format(TypeDesc::FLOAT);  // force float
+    ImageBuf Base(BaseSpec);
+    if (!bilateral_filter(Base, src, radius, sigma_range, roi, nthreads)) {
+        dst.errorfmt("{}", Base.geterror());
+        return false;
+    }
+
+    // The local detail is the difference between the source image and the
+    // base. (We store it in the same buffer we used for the base.)
+    ImageBuf& Detail(Base);
+    if (!sub(Detail, src, Base, roi, nthreads)) {
+        dst.errorfmt("{}", Detail.geterror());
+        return false;
+    }
+
+    // Add the scaled detail back to the original
+    return mad(dst, Detail, amount, src, roi, nthreads);
+}
+
+
+
+ImageBuf
+ImageBufAlgo::clarity(const ImageBuf& src, float amount, float radius,
+                      float sigma_range, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = clarity(result, src, amount, radius, sigma_range, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::clarity() error");
+    return result;
+}
+
+
+
 bool
 ImageBufAlgo::laplacian(ImageBuf& dst, const ImageBuf& src, ROI roi,
                         int nthreads)
@@ -653,6 +721,68 @@ ImageBufAlgo::laplacian(const ImageBuf& src, ROI roi, int nthreads)
 
 
 
+bool
+ImageBufAlgo::sobel(ImageBuf& dst_x, ImageBuf& dst_y, const ImageBuf& src,
+                    ROI roi, int nthreads)
+{
+    // N.B.: Don't log time, convolve will catch it
+    // Gradients can be negative, so default to float results
+    ROI roi_y = roi;
+    if (!IBAprep(roi, &dst_x, &src,
+                 IBAprep_REQUIRE_SAME_NCHANNELS | IBAprep_NO_SUPPORT_VOLUME
+                     | IBAprep_DST_FLOAT_PIXELS)
+        || !IBAprep(roi_y, &dst_y, &src,
+                    IBAprep_REQUIRE_SAME_NCHANNELS | IBAprep_NO_SUPPORT_VOLUME
+                        | IBAprep_DST_FLOAT_PIXELS))
+        return false;
+
+    ImageBuf Kx = make_kernel("sobel-x", 3, 3);
+    ImageBuf Ky = make_kernel("sobel-y", 3, 3);
+    bool ok     = convolve(dst_x, src, Kx, false, roi, nthreads);
+    ok &= convolve(dst_y, src, Ky, false, roi_y, nthreads);
+    return ok;
+}
+
+
+
+bool
+ImageBufAlgo::gradient_magnitude(ImageBuf& dst, const ImageBuf& src, ROI roi,
+                                 int nthreads)
+{
+    // N.B.: Don't log time, the constituent parts will catch it
+    // Magnitudes can exceed 1, so default to float results
+    if (!IBAprep(roi, &dst, &src,
+                 IBAprep_REQUIRE_SAME_NCHANNELS | IBAprep_NO_SUPPORT_VOLUME
+                     | IBAprep_DST_FLOAT_PIXELS))
+        return false;
+
+    ImageBuf Gx, Gy;
+    bool ok = sobel(Gx, Gy, src, roi, nthreads);
+    // Gx = Gx*Gx + Gy*Gy
+    ok = ok && mul(Gx, Gx, Gx, roi, nthreads) && mul(Gy, Gy, Gy, roi, nthreads)
+         && add(Gx, Gx, Gy, roi, nthreads);
+    if (!ok) {
+        dst.errorfmt("{}", Gx.has_error() ? Gx.geterror() : Gy.geterror());
+        return false;
+    }
+    std::vector<float> sqrtpow(roi.chend, 0.5f);
+    return pow(dst, Gx, sqrtpow, roi, nthreads);
+}
+
+
+
+ImageBuf
+ImageBufAlgo::gradient_magnitude(const ImageBuf& src, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = gradient_magnitude(result, src, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::gradient_magnitude() error");
+    return result;
+}
+
+
+
 template<class Rtype, class Atype>
 static bool
 median_filter_impl(ImageBuf& R, const ImageBuf& A, int width, int height,
@@ -732,6 +862,247 @@ ImageBufAlgo::median_filter(const ImageBuf& src, int width, int height, ROI roi,
 
 
 
+template<class Rtype, class Atype>
+static bool
+bilateral_filter_impl(ImageBuf& R, const ImageBuf& A, float sigma_spatial,
+                      float sigma_range, ROI roi, int nthreads)
+{
+    int radius = std::max(1, int(ceilf(2.0f * sigma_spatial)));
+    int width  = 2 * radius + 1;
+    // Precompute the spatial weights for the whole window
+    std::vector<float> spatial(width * width);
+    float sscale = -0.5f / (sigma_spatial * sigma_spatial);
+    for (int y = -radius; y <= radius; ++y)
+        for (int x = -radius; x <= radius; ++x)
+            spatial[(y + radius) * width + (x + radius)] = expf(
+                float(x * x + y * y) * sscale);
+    float rscale = -0.5f / (sigma_range * sigma_range);
+
+    ImageBufAlgo::parallel_image(roi, nthreads, [&](ROI roi) {
+        int alpha_channel = A.spec().alpha_channel;
+        int z_channel     = A.spec().z_channel;
+        int nchannels     = roi.chend;
+        float* center     = OIIO_ALLOCA(float, nchannels);
+        float* sum        = OIIO_ALLOCA(float, nchannels);
+        ImageBuf::ConstIterator<Atype> a(A, roi);
+        for (ImageBuf::Iterator<Rtype> r(R, roi); !r.done(); ++r) {
+            a.rerange(r.x() - radius, r.x() + radius + 1, r.y() - radius,
+                      r.y() + radius + 1, r.z(), r.z() + 1,
+                      ImageBuf::WrapClamp);
+            for (int c = roi.chbegin; c < roi.chend; ++c) {
+                center[c] = A.getchannel(r.x(), r.y(), r.z(), c,
+                                         ImageBuf::WrapClamp);
+                sum[c]    = 0.0f;
+            }
+            float wsum = 0.0f;
+            for (; !a.done(); ++a) {
+                if (!a.exists())
+                    continue;
+                float d2 = 0.0f;
+                for (int c = roi.chbegin; c < roi.chend; ++c) {
+                    if (c == alpha_channel || c == z_channel)
+                        continue;
+                    float d = a[c] - center[c];
+                    d2 += d * d;
+                }
+                float w = spatial[(a.y() - r.y() + radius) * width
+                                  + (a.x() - r.x() + radius)]
+                          * expf(d2 * rscale);
+                for (int c = roi.chbegin; c < roi.chend; ++c)
+                    sum[c] += w * a[c];
+                wsum += w;
+            }
+            for (int c = roi.chbegin; c < roi.chend; ++c)
+                r[c] = wsum > 0.0f ? sum[c] / wsum : center[c];
+        }
+    });
+    return true;
+}
+
+
+
+bool
+ImageBufAlgo::bilateral_filter(ImageBuf& dst, const ImageBuf& src,
+                               float sigma_spatial, float sigma_range, ROI roi,
+                               int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::bilateral_filter");
+    if (!(sigma_spatial > 0.0f) || !(sigma_range > 0.0f)) {
+        dst.errorfmt(
+            "ImageBufAlgo::bilateral_filter: sigmas must be positive (got {}, {})",
+            sigma_spatial, sigma_range);
+        return false;
+    }
+    if (!IBAprep(roi, &dst, &src,
+                 IBAprep_REQUIRE_SAME_NCHANNELS | IBAprep_NO_SUPPORT_VOLUME))
+        return false;
+
+    bool ok;
+    OIIO_DISPATCH_COMMON_TYPES2(ok, "bilateral_filter", bilateral_filter_impl,
+                                dst.spec().format, src.spec().format, dst, src,
+                                sigma_spatial, sigma_range, roi, nthreads);
+    return ok;
+}
+
+
+
+ImageBuf
+ImageBufAlgo::bilateral_filter(const ImageBuf& src, float sigma_spatial,
+                               float sigma_range, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = bilateral_filter(result, src, sigma_spatial, sigma_range, roi,
+                               nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::bilateral_filter() error");
+    return result;
+}
+
+
+
+template<class Rtype>
+static bool
+joint_bilateral_upsample_impl(ImageBuf& R, const ImageBuf& L,
+                              const ImageBuf& G, float sigma_spatial,
+                              float sigma_range, ROI roi, int nthreads)
+{
+    const ImageSpec& lspec = L.spec();
+    const ImageSpec& gspec = G.spec();
+    // Scale factors from guide pixels to low-res pixels, relating the two
+    // full (display) windows.
+    float xscale = float(lspec.full_width) / float(gspec.full_width);
+    float yscale = float(lspec.full_height) / float(gspec.full_height);
+    int radius   = std::max(1, int(ceilf(2.0f * sigma_spatial)));
+    float sscale = -0.5f / (sigma_spatial * sigma_spatial);
+    float rscale = -0.5f / (sigma_range * sigma_range);
+
+    // Grab all the low-res pixels, and sample the guide at the center of
+    // each of them, so the inner loop doesn't need to do either.
+    ROI lroi       = L.roi();
+    int lchans     = lspec.nchannels;
+    int gchans     = gspec.nchannels;
+    imagesize_t ln = lroi.npixels();
+    std::vector<float> lpixels(ln * lchans);
+    std::vector<float> gsamples(ln * gchans);
+    L.get_pixels(lroi, TypeFloat, lpixels.data());
+    for (int y = lroi.ybegin, i = 0; y < lroi.yend; ++y) {
+        for (int x = lroi.xbegin; x < lroi.xend; ++x, ++i) {
+            float gx = gspec.full_x + (x - lspec.full_x + 0.5f) / xscale;
+            float gy = gspec.full_y + (y - lspec.full_y + 0.5f) / yscale;
+            G.interppixel(gx, gy, &gsamples[i * gchans], ImageBuf::WrapClamp);
+        }
+    }
+
+    ImageBufAlgo::parallel_image(roi, nthreads, [&](ROI roi) {
+        int alpha_channel = gspec.alpha_channel;
+        int z_channel     = gspec.z_channel;
+        float* center     = OIIO_ALLOCA(float, gchans);
+        float* sum        = OIIO_ALLOCA(float, roi.chend);
+        for (ImageBuf::Iterator<Rtype> r(R, roi); !r.done(); ++r) {
+            // Position of this pixel's center in low-res pixel coordinates
+            float lx = lspec.full_x + (r.x() + 0.5f - gspec.full_x) * xscale
+                       - 0.5f;
+            float ly = lspec.full_y + (r.y() + 0.5f - gspec.full_y) * yscale
+                       - 0.5f;
+            int cx = int(floorf(lx + 0.5f));
+            int cy = int(floorf(ly + 0.5f));
+            G.getpixel(r.x(), r.y(), r.z(), center, gchans,
+                       ImageBuf::WrapClamp);
+            for (int c = roi.chbegin; c < roi.chend; ++c)
+                sum[c] = 0.0f;
+            float wsum = 0.0f;
+            int y0     = std::max(cy - radius, lroi.ybegin);
+            int y1     = std::min(cy + radius + 1, lroi.yend);
+            int x0     = std::max(cx - radius, lroi.xbegin);
+            int x1     = std::min(cx + radius + 1, lroi.xend);
+            for (int y = y0; y < y1; ++y) {
+                for (int x = x0; x < x1; ++x) {
+                    size_t i = size_t(y - lroi.ybegin) * lroi.width()
+                               + size_t(x - lroi.xbegin);
+                    // Range distance between guide colors, skipping alpha
+                    const float* g = &gsamples[i * gchans];
+                    float d2       = 0.0f;
+                    for (int c = 0; c < gchans; ++c) {
+                        if (c == alpha_channel || c == z_channel)
+                            continue;
+                        float d = g[c] - center[c];
+                        d2 += d * d;
+                    }
+                    float dx = float(x) - lx, dy = float(y) - ly;
+                    float w  = expf((dx * dx + dy * dy) * sscale
+                                    + d2 * rscale);
+                    const float* l = &lpixels[i * lchans];
+                    for (int c = roi.chbegin; c < roi.chend; ++c)
+                        sum[c] += w * l[c];
+                    wsum += w;
+                }
+            }
+            for (int c = roi.chbegin; c < roi.chend; ++c)
+                r[c] = wsum > 0.0f ? sum[c] / wsum : 0.0f;
+        }
+    });
+    return true;
+}
+
+
+
+bool
+ImageBufAlgo::joint_bilateral_upsample(ImageBuf& dst, const ImageBuf& src,
+                                       const ImageBuf& guide,
+                                       float sigma_spatial, float sigma_range,
+                                       ROI roi, int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::joint_bilateral_upsample");
+    if (!(sigma_spatial > 0.0f) || !(sigma_range > 0.0f)) {
+        dst.errorfmt(
+            "ImageBufAlgo::joint_bilateral_upsample: sigmas must be positive (got {}, {})",
+            sigma_spatial, sigma_range);
+        return false;
+    }
+    if (!src.initialized() || !guide.initialized()) {
+        dst.errorfmt("Uninitialized input image");
+        return false;
+    }
+    if (!dst.initialized()) {
+        // The result has the guide's resolution and the source's channels
+        ImageSpec spec = src.spec();
+        set_roi(spec, guide.roi());
+        set_roi_full(spec, guide.roi_full());
+        spec.tile_width  = 0;
+        spec.tile_height = 0;
+        spec.tile_depth  = 0;
+        dst.reset(spec);
+    }
+    if (!IBAprep(roi, &dst, &src, &guide,
+                 IBAprep_NO_SUPPORT_VOLUME))
+        return false;
+    roi.chend = std::min(roi.chend, src.nchannels());
+
+    bool ok;
+    OIIO_DISPATCH_TYPES(ok, "joint_bilateral_upsample",
+                        joint_bilateral_upsample_impl, dst.spec().format, dst,
+                        src, guide, sigma_spatial, sigma_range, roi, nthreads);
+    return ok;
+}
+
+
+
+ImageBuf
+ImageBufAlgo::joint_bilateral_upsample(const ImageBuf& src,
+                                       const ImageBuf& guide,
+                                       float sigma_spatial, float sigma_range,
+                                       ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = joint_bilateral_upsample(result, src, guide, sigma_spatial,
+                                       sigma_range, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::joint_bilateral_upsample() error");
+    return result;
+}
+
+
+
 enum MorphOp { MorphDilate, MorphErode };
 
 template<class Rtype, class Atype>
@@ -1244,4 +1615,384 @@ ImageBufAlgo::fillholes_pushpull(const ImageBuf& src, ROI roi, int nthreads)
 }
 
 
+
+bool
+ImageBufAlgo::poisson_clone(ImageBuf& dst, const ImageBuf& background,
+                            const ImageBuf& src, const ImageBuf& mask,
+                            int xoffset, int yoffset, int iterations, ROI roi,
+                            int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::poisson_clone");
+    if (!src.initialized() || !mask.initialized()) {
+        dst.errorfmt("Uninitialized input image");
+        return false;
+    }
+    if (src.deep() || mask.deep()) {
+        dst.errorfmt("poisson_clone does not support deep images");
+        return false;
+    }
+    if (!IBAprep(roi, &dst, &background, IBAprep_NO_SUPPORT_VOLUME))
+        return false;
+    if (!copy(dst, background, TypeUnknown, roi, nthreads))
+        return false;
+
+    // Find the bounding box of the clone region in result coordinates,
+    // grown by one pixel for its boundary and clipped to the ROI.
+    ROI box;
+    for (ImageBuf::ConstIterator<float> m(mask); !m.done(); ++m) {
+        if (m[0] > 0.5f) {
+            ROI p(m.x() + xoffset, m.x() + xoffset + 1, m.y() + yoffset,
+                  m.y() + yoffset + 1);
+            box = box.defined() ? roi_union(box, p) : p;
+        }
+    }
+    if (!box.defined())
+        return true;  // Nothing to clone
+    box.xbegin -= 1;
+    box.xend += 1;
+    box.ybegin -= 1;
+    box.yend += 1;
+    box.zbegin  = roi.zbegin;
+    box.zend    = roi.zend;
+    box.chbegin = roi.chbegin;
+    box.chend   = std::min(roi.chend, src.nchannels());
+    box         = roi_intersection(box, roi);
+    if (box.npixels() == 0 || box.nchannels() <= 0)
+        return true;
+
+    // Gather the background, the guidance (src), and the mask over the box
+    int w = box.width(), h = box.height(), nc = box.nchannels();
+    ROI srcbox = box;
+    srcbox.xbegin -= xoffset;
+    srcbox.xend -= xoffset;
+    srcbox.ybegin -= yoffset;
+    srcbox.yend -= yoffset;
+    ROI maskbox     = srcbox;
+    maskbox.chbegin = 0;
+    maskbox.chend   = 1;
+    std::vector<float> bg(box.npixels() * nc), g(box.npixels() * nc);
+    std::vector<float> mvals(box.npixels());
+    background.get_pixels(box, TypeFloat, bg.data());
+    src.get_pixels(srcbox, TypeFloat, g.data());
+    mask.get_pixels(maskbox, TypeFloat, mvals.data());
+
+    // For each pixel p inside the region, with neighbors N(p) within the
+    // box, we want  |N(p)| f_p - sum_{q in region} f_q
+    //                  = sum_q (g_p - g_q) + sum_{q not in region} bg_q
+    // Precompute the right hand side and the neighbor counts.
+    auto inside = [&](int x, int y) {
+        return x >= 0 && x < w && y >= 0 && y < h && mvals[y * w + x] > 0.5f;
+    };
+    const int dx[4] = { -1, 1, 0, 0 };
+    const int dy[4] = { 0, 0, -1, 1 };
+    std::vector<float> rhs(box.npixels() * nc, 0.0f);
+    std::vector<int> nneighbors(box.npixels(), 0);
+    std::vector<float> f = bg;
+    for (int y = 0; y < h; ++y) {
+        for (int x = 0; x < w; ++x) {
+            if (!inside(x, y))
+                continue;
+            int p = y * w + x;
+            for (int n = 0; n < 4; ++n) {
+                int qx = x + dx[n], qy = y + dy[n];
+                if (qx < 0 || qx >= w || qy < 0 || qy >= h)
+                    continue;  // outside the ROI: no constraint
+                int q = qy * w + qx;
+                ++nneighbors[p];
+                for (int c = 0; c < nc; ++c) {
+                    rhs[p * nc + c] += g[p * nc + c] - g[q * nc + c];
+                    if (!inside(qx, qy))
+                        rhs[p * nc + c] += bg[q * nc + c];
+                }
+            }
+            // Start from the source values, which are already close in
+            // shape to the solution.
+            for (int c = 0; c < nc; ++c)
+                f[p * nc + c] = g[p * nc + c];
+        }
+    }
+
+    // Red-black successive over-relaxation. Each half-sweep only reads
+    // pixels of the other color, so the rows can be done in parallel.
+    const float omega = 1.9f;
+    for (int i = 0; i < iterations; ++i) {
+        for (int color = 0; color < 2; ++color) {
+            parallel_image(ROI(0, w, 0, h), nthreads, [&](ROI r) {
+                for (int y = r.ybegin; y < r.yend; ++y) {
+                    for (int x = r.xbegin + ((r.xbegin + y + color) & 1);
+                         x < r.xend; x += 2) {
+                        int p = y * w + x;
+                        if (!nneighbors[p] || !inside(x, y))
+                            continue;
+                        for (int c = 0; c < nc; ++c) {
+                            float sum = rhs[p * nc + c];
+                            for (int n = 0; n < 4; ++n) {
+                                if (inside(x + dx[n], y + dy[n]))
+                                    sum += f[((y + dy[n]) * w + x + dx[n]) * nc
+                                             + c];
+                            }
+                            float& fp = f[p * nc + c];
+                            fp += omega * (sum / nneighbors[p] - fp);
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    stride_t xstride = nc * sizeof(float);
+    return dst.set_pixels(box, TypeFloat, f.data(), xstride, xstride * w);
+}
+
+
+
+ImageBuf
+ImageBufAlgo::poisson_clone(const ImageBuf& background, const ImageBuf& src,
+                            const ImageBuf& mask, int xoffset, int yoffset,
+                            int iterations, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = poisson_clone(result, background, src, mask, xoffset, yoffset,
+                            iterations, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::poisson_clone() error");
+    return result;
+}
+
+
+
+// Helper for exposure_fusion: make the levels of a pyramid by successive
+// x/2 resizing of img, down to a single pixel. Any error is set in dst.
+static bool
+fusion_pyramid(ImageBuf& dst, std::vector<ImageBuf>& pyramid,
+               const ImageBuf& img, int nthreads)
+{
+    pyramid.clear();
+    pyramid.push_back(img);
+    int w = img.spec().width, h = img.spec().height;
+    while (w > 1 || h > 1) {
+        w = std::max(1, w / 2);
+        h = std::max(1, h / 2);
+        ImageBuf small(ImageSpec(w, h, img.nchannels(), TypeDesc::FLOAT));
+        if (!ImageBufAlgo::resize(small, pyramid.back(),
+                                  { { "filtername", "triangle" } }, {},
+                                  nthreads)) {
+            dst.errorfmt("{}", small.geterror());
+            return false;
+        }
+        pyramid.push_back(std::move(small));
+    }
+    return true;
+}
+
+
+
+// Helper for exposure_fusion: compute the Mertens weight of every pixel of
+// the float image F, whose channels `colorchans` hold the color.
+static void
+fusion_weight(ImageBuf& weight, const ImageBuf& F, cspan<int> colorchans,
+              float contrast_weight, float saturation_weight,
+              float exposure_weight, int nthreads)
+{
+    const ImageSpec& spec(F.spec());
+    int w = spec.width, h = spec.height, nc = spec.nchannels;
+
+    int ncolor          = int(colorchans.size());
+    const float* pixels = (const float*)F.localpixels();
+
+    // Average of the color channels, clamping at the image edges
+    auto gray = [&](int x, int y) {
+        x              = clamp(x, 0, w - 1);
+        y              = clamp(y, 0, h - 1);
+        const float* p = pixels + (size_t(y) * w + x) * nc;
+        float sum      = 0.0f;
+        for (int c : colorchans)
+            sum += p[c];
+        return sum / ncolor;
+    };
+    ImageBufAlgo::parallel_image(weight.roi(), nthreads, [&](ROI roi) {
+        for (ImageBuf::Iterator<float> wt(weight, roi); !wt.done(); ++wt) {
+            int x = wt.x(), y = wt.y();
+            // Contrast is the magnitude of the Laplacian of the gray value
+            float mean     = gray(x, y);
+            float contrast = fabsf(gray(x - 1, y) + gray(x + 1, y)
+                                   + gray(x, y - 1) + gray(x, y + 1)
+                                   - 4.0f * mean);
+
+            // Saturation is the standard deviation of the color channels
+            // (meaningless for just one), exposure is how close they all
+            // are to 0.5.
+            const float* p = pixels + (size_t(y) * w + x) * nc;
+            float var      = 0.0f;
+            float exposure = 1.0f;
+            for (int c : colorchans) {
+                float d = p[c] - mean;
+                float e = p[c] - 0.5f;
+                var += d * d;
+                exposure *= expf(-e * e / (2.0f * 0.2f * 0.2f));
+            }
+            float saturation = ncolor > 1 ? sqrtf(var / ncolor) : 1.0f;
+            wt[0] = powf(contrast, contrast_weight)
+                        * powf(saturation, saturation_weight)
+                        * powf(exposure, exposure_weight)
+                    + 1.0e-12f;
+        }
+    });
+}
+
+
+
+bool
+ImageBufAlgo::exposure_fusion(ImageBuf& dst, cspan<ImageBuf> images,
+                              float contrast_weight, float saturation_weight,
+                              float exposure_weight, ROI roi, int nthreads)
+{
+    // N.B. Don't log time, it will be caught by the constituent parts
+    if (images.empty()) {
+        dst.errorfmt("exposure_fusion: no input images");
+        return false;
+    }
+    for (const ImageBuf& img : images) {
+        if (!img.initialized()) {
+            dst.errorfmt("Uninitialized input image");
+            return false;
+        }
+        if (img.deep()) {
+            dst.errorfmt("exposure_fusion does not support deep images");
+            return false;
+        }
+        if (img.roi() != images[0].roi()) {
+            dst.errorfmt(
+                "exposure_fusion: all images must have the same data window and number of channels");
+            return false;
+        }
+    }
+    if (contrast_weight < 0.0f || saturation_weight < 0.0f
+        || exposure_weight < 0.0f) {
+        dst.errorfmt("exposure_fusion: weights must not be negative");
+        return false;
+    }
+    if (!IBAprep(roi, &dst, &images[0], IBAprep_NO_SUPPORT_VOLUME))
+        return false;
+    roi.chend = std::min(roi.chend, images[0].nchannels());
+
+    // The channels (relative to the ROI) that determine the weights: up to
+    // three, skipping alpha.
+    int nc = roi.nchannels();
+    std::vector<int> colorchans;
+    for (int c = 0; c < nc && colorchans.size() < 3; ++c)
+        if (c + roi.chbegin != images[0].spec().alpha_channel)
+            colorchans.push_back(c);
+    if (colorchans.empty()) {
+        dst.errorfmt("exposure_fusion: no color channels in the ROI");
+        return false;
+    }
+
+    // Make float copies of the ROI of each image, with origin at 0, and
+    // their per-pixel weights.
+    ImageSpec spec(roi.width(), roi.height(), nc, TypeDesc::FLOAT);
+    std::vector<float> pixels(roi.npixels() * nc);
+    std::vector<ImageBuf> fimages, weights;
+    for (const ImageBuf& img : images) {
+        if (!img.get_pixels(roi, TypeFloat, pixels.data())) {
+            dst.errorfmt("{}", img.geterror());
+            return false;
+        }
+        fimages.emplace_back(spec);
+        if (!fimages.back().set_pixels(get_roi(spec), TypeFloat,
+                                       pixels.data())) {
+            dst.errorfmt("{}", fimages.back().geterror());
+            return false;
+        }
+        weights.emplace_back(ImageSpec(spec.width, spec.height, 1,
+                                       TypeDesc::FLOAT));
+        fusion_weight(weights.back(), fimages.back(), colorchans,
+                      contrast_weight, saturation_weight, exposure_weight,
+                      nthreads);
+    }
+
+    // Normalize the weights so they sum to 1 at each pixel.
+    ImageBuf wsum = weights[0];
+    for (size_t i = 1; i < weights.size(); ++i) {
+        if (!add(wsum, wsum, weights[i], {}, nthreads)) {
+            dst.errorfmt("{}", wsum.geterror());
+            return false;
+        }
+    }
+    for (auto& wt : weights) {
+        if (!div(wt, wt, wsum, {}, nthreads)) {
+            dst.errorfmt("{}", wt.geterror());
+            return false;
+        }
+    }
+
+    // Blend the Laplacian pyramids of the images, weighted by the
+    // Gaussian pyramids of their weights.
+    std::vector<ImageBuf> blended, lap, wpyr;
+    for (size_t i = 0; i < fimages.size(); ++i) {
+        if (!fusion_pyramid(dst, lap, fimages[i], nthreads)
+            || !fusion_pyramid(dst, wpyr, weights[i], nthreads))
+            return false;
+        fimages[i].reset();
+        for (size_t l = 0; l + 1 < lap.size(); ++l) {
+            ImageBuf blowup(lap[l].spec());
+            if (!resize(blowup, lap[l + 1], { { "filtername", "triangle" } },
+                        {}, nthreads)) {
+                dst.errorfmt("{}", blowup.geterror());
+                return false;
+            }
+            if (!sub(lap[l], lap[l], blowup, {}, nthreads)) {
+                dst.errorfmt("{}", lap[l].geterror());
+                return false;
+            }
+        }
+        if (blended.empty())
+            for (auto& level : lap)
+                blended.emplace_back(level.spec());
+        for (size_t l = 0; l < lap.size(); ++l) {
+            ImageBuf &sum(blended[l]), &L(lap[l]), &W(wpyr[l]);
+            parallel_image(sum.roi(), nthreads, [&](ROI r) {
+                ImageBuf::ConstIterator<float> a(L, r), wt(W, r);
+                for (ImageBuf::Iterator<float> s(sum, r); !s.done();
+                     ++s, ++a, ++wt)
+                    for (int c = 0; c < nc; ++c)
+                        s[c] += wt[0] * a[c];
+            });
+        }
+    }
+
+    // Collapse the blended pyramid, from the smallest level up.
+    for (int l = int(blended.size()) - 2; l >= 0; --l) {
+        ImageBuf blowup(blended[l].spec());
+        if (!resize(blowup, blended[l + 1], { { "filtername", "triangle" } },
+                    {}, nthreads)) {
+            dst.errorfmt("{}", blowup.geterror());
+            return false;
+        }
+        if (!add(blended[l], blended[l], blowup, {}, nthreads)) {
+            dst.errorfmt("{}", blended[l].geterror());
+            return false;
+        }
+    }
+    return paste(dst, roi.xbegin, roi.ybegin, roi.zbegin, roi.chbegin,
+                 blended[0], {}, nthreads);
+}
+
+
+
+ImageBuf
+ImageBufAlgo::exposure_fusion(cspan<ImageBuf> images, float contrast_weight,
+                              float saturation_weight, float exposure_weight,
+                              ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = exposure_fusion(result, images, contrast_weight,
+                              saturation_weight, exposure_weight, roi,
+                              nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::exposure_fusion() error");
+    return result;
+}
+
+
 OIIO_NAMESPACE_END
@@ -200,6 +200,45 @@ ImageBufAlgo::channels(const ImageBuf& src, int nchannels,
 
 
 
+bool
+ImageBufAlgo::channels_by_name(ImageBuf& dst, const ImageBuf& src,
+                               cspan<std::string> channelnames,
+                               bool fill_missing, float fillvalue,
+                               int nthreads)
+{
+    int nchannels = int(channelnames.size());
+    std::vector<int> channelorder(nchannels, -1);
+    std::vector<float> channelvalues(nchannels, fillvalue);
+    for (int c = 0; c < nchannels; ++c) {
+        channelorder[c] = src.spec().channelindex(channelnames[c]);
+        if (channelorder[c] < 0 && !fill_missing) {
+            dst.errorfmt("channels_by_name: no channel named \"{}\"",
+                         channelnames[c]);
+            return false;
+        }
+    }
+    return channels(dst, src, nchannels, channelorder, channelvalues,
+                    channelnames, false, nthreads);
+}
+
+
+
+ImageBuf
+ImageBufAlgo::channels_by_name(const ImageBuf& src,
+                               cspan<std::string> channelnames,
+                               bool fill_missing, float fillvalue,
+                               int nthreads)
+{
+    ImageBuf result;
+    bool ok = channels_by_name(result, src, channelnames, fill_missing,
+                               fillvalue, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::channels_by_name() error");
+    return result;
+}
+
+
+
 template<class Rtype, class Atype, class Btype>
 static bool
 channel_append_impl(ImageBuf& dst, const ImageBuf& A, const ImageBuf& B,
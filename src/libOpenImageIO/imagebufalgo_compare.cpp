@@ -6,7 +6,9 @@
 /// Implementation of ImageBufAlgo algorithms that analyze or compare
 /// images.
 
+#include <algorithm>
 #include <cmath>
+#include <cstring>
 #include <iostream>
 #include <limits>
 
@@ -209,6 +211,129 @@ ImageBufAlgo::computePixelStats(const ImageBuf& src, ROI roi, int nthreads)
 
 
 
+ImageBufAlgo::PixelStats
+ImageBufAlgo::computePixelStats(ImageInput& in, int subimage, int miplevel,
+                                ROI roi, int nthreads)
+{
+    pvt::LoggedTimer logtimer("IBA::computePixelStats");
+    ImageBufAlgo::PixelStats stats;
+    ImageSpec spec = in.spec_dimensions(subimage, miplevel);
+    if (spec.format == TypeUnknown) {
+        if (!in.has_error())
+            in.errorfmt("computePixelStats: no subimage {} MIP level {}",
+                        subimage, miplevel);
+        return stats;
+    }
+    if (spec.deep) {
+        in.errorfmt("computePixelStats: deep images not supported");
+        return stats;
+    }
+    int nchannels = spec.nchannels;
+    if (nchannels == 0) {
+        in.errorfmt("{}-channel images not supported", nchannels);
+        return stats;
+    }
+    if (!roi.defined())
+        roi = get_roi(spec);
+    else
+        roi = roi_intersection(roi, get_roi(spec));
+    stats.reset(nchannels);
+    if (roi.npixels() == 0 || roi.nchannels() <= 0) {
+        finalize(stats);
+        return stats;
+    }
+
+    // Read bands of roughly 16 MB at a time. Scanline files are read as
+    // bands of whole scanlines. Tiled files can't be read by scanline, so
+    // they are read as bands of whole rows of tiles spanning the ROI, with
+    // all bounds rounded out to tile boundaries (or the image edge).
+    const imagesize_t bandbytes = imagesize_t(16) << 20;
+    int nc                      = roi.nchannels();
+    bool tiled                  = spec.tile_width > 0;
+    int th                      = std::max(1, spec.tile_height);
+    int td                      = std::max(1, spec.tile_depth);
+
+    // The range of pixels to read
+    int bxbegin = spec.x, bxend = spec.x + spec.width;
+    int ybegin0 = roi.ybegin, ylimit = roi.yend;
+    int zbegin0 = roi.zbegin, zlimit = roi.zend, zstep = 1;
+    if (tiled) {
+        int tw  = spec.tile_width;
+        bxbegin = spec.x + round_down_to_multiple(roi.xbegin - spec.x, tw);
+        bxend   = std::min(spec.x + round_to_multiple(roi.xend - spec.x, tw),
+                           spec.x + spec.width);
+        ybegin0 = spec.y + round_down_to_multiple(roi.ybegin - spec.y, th);
+        ylimit  = std::min(spec.y + round_to_multiple(roi.yend - spec.y, th),
+                           spec.y + spec.height);
+        zbegin0 = spec.z + round_down_to_multiple(roi.zbegin - spec.z, td);
+        zlimit  = std::min(spec.z + round_to_multiple(roi.zend - spec.z, td),
+                           spec.z + spec.depth);
+        zstep   = td;
+    }
+    int bw                = bxend - bxbegin;
+    imagesize_t linebytes = imagesize_t(bw) * zstep * nc * sizeof(float);
+    int bandheight        = int(clamp(bandbytes / linebytes, imagesize_t(1),
+                                      imagesize_t(ylimit - ybegin0)));
+    if (tiled)
+        bandheight = round_to_multiple(bandheight, th);
+    std::vector<float> band(size_t(bandheight) * bw * zstep * nc);
+
+    OIIO::spin_mutex mutex;  // protect the shared stats when merging
+    parallel_options opt(nthreads);
+    for (int zb = zbegin0; zb < zlimit; zb += zstep) {
+        int ze = std::min(zb + zstep, zlimit);
+        for (int ybegin = ybegin0; ybegin < ylimit; ybegin += bandheight) {
+            int yend = std::min(ybegin + bandheight, ylimit);
+            bool ok  = tiled ? in.read_tiles(subimage, miplevel, bxbegin,
+                                             bxend, ybegin, yend, zb, ze,
+                                             roi.chbegin, roi.chend,
+                                             TypeFloat, band.data())
+                             : in.read_scanlines(subimage, miplevel, ybegin,
+                                                 yend, zb, roi.chbegin,
+                                                 roi.chend, TypeFloat,
+                                                 band.data());
+            if (!ok) {
+                stats.reset(0);
+                return stats;
+            }
+            // Only accumulate the part of the band inside the ROI
+            int y0 = std::max(ybegin, roi.ybegin);
+            int y1 = std::min(yend, roi.yend);
+            int z0 = std::max(zb, roi.zbegin);
+            int z1 = std::min(ze, roi.zend);
+            parallel_for_chunked(
+                y0, y1, 16,
+                [&](int64_t yb, int64_t ye) {
+                    ImageBufAlgo::PixelStats tmp(nchannels);
+                    for (int z = z0; z < z1; ++z) {
+                        for (int64_t y = yb; y < ye; ++y) {
+                            const float* p
+                                = band.data()
+                                  + ((size_t(z - zb) * (yend - ybegin)
+                                      + (y - ybegin))
+                                         * bw
+                                     + (roi.xbegin - bxbegin))
+                                        * nc;
+                            for (int x = roi.xbegin; x < roi.xend;
+                                 ++x, p += nc)
+                                for (int c = 0; c < nc; ++c)
+                                    val(tmp, roi.chbegin + c, p[c]);
+                        }
+                    }
+                    std::lock_guard<OIIO::spin_mutex> lock(mutex);
+                    stats.merge(tmp);
+                },
+                opt);
+        }
+    }
+
+    // Compute final results
+    finalize(stats);
+    return stats;
+}
+
+
+
 // Deprecated:
 bool
 ImageBufAlgo::computePixelStats(PixelStats& stats, const ImageBuf& src, ROI roi,
@@ -398,6 +523,41 @@ ImageBufAlgo::compare(const ImageBuf& A, const ImageBuf& B, float failthresh,
 
 
 
+// Are the two attribute lists identical, disregarding order?
+static bool
+same_metadata(const ParamValueList& a, const ParamValueList& b)
+{
+    if (a.size() != b.size())
+        return false;
+    for (auto&& pa : a) {
+        auto pb = b.find(pa.name(), pa.type(), true /*casesensitive*/);
+        if (pb == b.cend() || pa.nvalues() != pb->nvalues()
+            || memcmp(pa.data(), pb->data(), pa.datasize()))
+            return false;
+    }
+    return true;
+}
+
+
+
+bool
+ImageBufAlgo::content_equals(const ImageBuf& A, const ImageBuf& B,
+                             bool compare_metadata, float tolerance,
+                             int nthreads)
+{
+    pvt::LoggedTimer logtimer("IBA::content_equals");
+    if (A.roi() != B.roi() || A.deep() != B.deep())
+        return false;
+    if (compare_metadata
+        && (A.spec().channelnames != B.spec().channelnames
+            || !same_metadata(A.spec().extra_attribs, B.spec().extra_attribs)))
+        return false;
+    auto result = compare(A, B, tolerance, tolerance, A.roi(), nthreads);
+    return !result.error && result.nfail == 0;
+}
+
+
+
 template<typename T>
 static bool
 isConstantColor_(const ImageBuf& src, float threshold, span<float> color,
@@ -833,6 +993,306 @@ ImageBufAlgo::nonzero_region(const ImageBuf& src, ROI roi, int nthreads)
 
 
 
+// Find the root of x in the union-find forest, compressing the path.
+static int
+find_root(std::vector<int>& parent, int x)
+{
+    while (parent[x] != x) {
+        parent[x] = parent[parent[x]];
+        x         = parent[x];
+    }
+    return x;
+}
+
+
+
+bool
+ImageBufAlgo::connected_components(ImageBuf& dst, const ImageBuf& src,
+                                   int channel, float threshold,
+                                   int connectivity, int* ncomponents, ROI roi,
+                                   int /*nthreads*/)
+{
+    pvt::LoggedTimer logtimer("IBA::connected_components");
+    if (ncomponents)
+        *ncomponents = 0;
+    if (!src.initialized()) {
+        dst.errorfmt("Uninitialized input image");
+        return false;
+    }
+    if (channel < 0 || channel >= src.nchannels()) {
+        dst.errorfmt("Invalid channel {} for input image with channels 0 to {}",
+                     channel, src.nchannels() - 1);
+        return false;
+    }
+    if (connectivity != 4 && connectivity != 8) {
+        dst.errorfmt("connected_components: connectivity must be 4 or 8");
+        return false;
+    }
+    if (!roi.defined())
+        roi = src.roi();
+    roi.chbegin = 0;
+    roi.chend   = 1;
+    ImageSpec labelspec(roi, TypeFloat);
+    labelspec.channelnames[0] = "label";
+    if (!IBAprep(roi, &dst, &src, nullptr, &labelspec,
+                 IBAprep_NO_SUPPORT_VOLUME | IBAprep_NO_COPY_ROI_FULL))
+        return false;
+
+    int w = roi.width(), h = roi.height();
+    std::vector<float> values(roi.npixels());
+    ROI chanroi     = roi;
+    chanroi.chbegin = channel;
+    chanroi.chend   = channel + 1;
+    src.get_pixels(chanroi, TypeFloat, values.data());
+
+    // First pass: provisional labels, merging equivalent ones. Since we
+    // scan in order, only the already-visited neighbors (left, and the
+    // row above) need to be examined.
+    std::vector<int> labels(roi.npixels(), -1);
+    std::vector<int> parent;
+    for (int y = 0; y < h; ++y) {
+        for (int x = 0; x < w; ++x) {
+            int p = y * w + x;
+            if (!(values[p] > threshold))
+                continue;
+            int nbrs[4] = { -1, -1, -1, -1 };
+            if (x > 0)
+                nbrs[0] = labels[p - 1];
+            if (y > 0) {
+                nbrs[1] = labels[p - w];
+                if (connectivity == 8) {
+                    if (x > 0)
+                        nbrs[2] = labels[p - w - 1];
+                    if (x < w - 1)
+                        nbrs[3] = labels[p - w + 1];
+                }
+            }
+            int label = -1;
+            for (int n : nbrs) {
+                if (n < 0)
+                    continue;
+                if (label < 0) {
+                    label = find_root(parent, n);
+                } else {
+                    // Merge, keeping the smaller root so that numbering
+                    // follows scanline order.
+                    int r  = find_root(parent, n);
+                    int lo = std::min(r, label), hi = std::max(r, label);
+                    parent[hi] = lo;
+                    label      = lo;
+                }
+            }
+            if (label < 0) {
+                label = int(parent.size());
+                parent.push_back(label);
+            }
+            labels[p] = label;
+        }
+    }
+
+    // Assign consecutive final ids to the roots, in order of first
+    // appearance, and write the result.
+    std::vector<int> ids(parent.size(), 0);
+    int n = 0;
+    for (size_t i = 0; i < parent.size(); ++i)
+        if (find_root(parent, int(i)) == int(i))
+            ids[i] = ++n;
+    for (int p = 0, e = int(labels.size()); p < e; ++p)
+        values[p] = labels[p] < 0 ? 0.0f
+                                  : float(ids[find_root(parent, labels[p])]);
+    if (ncomponents)
+        *ncomponents = n;
+    return dst.set_pixels(roi, TypeFloat, values.data());
+}
+
+
+
+ImageBuf
+ImageBufAlgo::connected_components(const ImageBuf& src, int channel,
+                                   float threshold, int connectivity, ROI roi,
+                                   int nthreads)
+{
+    ImageBuf result;
+    bool ok = connected_components(result, src, channel, threshold,
+                                   connectivity, nullptr, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::connected_components() error");
+    return result;
+}
+
+
+
+// Mean squared difference between target(x,y) and reference(x-sx,y-sy)
+// over their overlap, for w x h images of nc interleaved float channels.
+static double
+translation_error(const std::vector<float>& R, const std::vector<float>& T,
+                  int w, int h, int nc, int sx, int sy)
+{
+    int x0 = std::max(0, sx), x1 = std::min(w, w + sx);
+    int y0 = std::max(0, sy), y1 = std::min(h, h + sy);
+    if (x1 <= x0 || y1 <= y0)
+        return std::numeric_limits<double>::max();
+    double sum = 0.0;
+    for (int y = y0; y < y1; ++y) {
+        const float* t = &T[(size_t(y) * w + x0) * nc];
+        const float* r = &R[(size_t(y - sy) * w + x0 - sx) * nc];
+        for (int k = 0, e = (x1 - x0) * nc; k < e; ++k) {
+            double d = double(t[k]) - double(r[k]);
+            sum += d * d;
+        }
+    }
+    return sum / (double(x1 - x0) * double(y1 - y0) * nc);
+}
+
+
+
+// Halve the resolution of a w x h image of nc float channels by averaging
+// 2x2 blocks (dropping the last row or column if the size is odd).
+static std::vector<float>
+translation_downsample(const std::vector<float>& src, int w, int h, int nc)
+{
+    int nw = w / 2, nh = h / 2;
+    std::vector<float> dst(size_t(nw) * nh * nc);
+    for (int y = 0; y < nh; ++y) {
+        for (int x = 0; x < nw; ++x) {
+            float* d        = &dst[(size_t(y) * nw + x) * nc];
+            const float* s0 = &src[(size_t(2 * y) * w + 2 * x) * nc];
+            const float* s1 = s0 + size_t(w) * nc;
+            for (int c = 0; c < nc; ++c)
+                d[c] = 0.25f * (s0[c] + s0[nc + c] + s1[c] + s1[nc + c]);
+        }
+    }
+    return dst;
+}
+
+
+
+bool
+ImageBufAlgo::estimate_translation(const ImageBuf& reference,
+                                   const ImageBuf& target, float& dx,
+                                   float& dy, int max_shift, ROI roi,
+                                   int nthreads)
+{
+    pvt::LoggedTimer logtimer("IBA::estimate_translation");
+    dx = 0.0f;
+    dy = 0.0f;
+    if (!reference.initialized() || !target.initialized()) {
+        reference.errorfmt("Uninitialized input image");
+        return false;
+    }
+    if (reference.deep() || target.deep()) {
+        reference.errorfmt("estimate_translation does not support deep images");
+        return false;
+    }
+    if (max_shift < 0) {
+        reference.errorfmt(
+            "estimate_translation: max_shift must not be negative (got {})",
+            max_shift);
+        return false;
+    }
+    if (!roi.defined())
+        roi = roi_intersection(reference.roi(), target.roi());
+    roi.chend = std::min(roi.chend,
+                         std::min(reference.nchannels(), target.nchannels()));
+    if (roi.depth() > 1) {
+        reference.errorfmt("estimate_translation does not support volumes");
+        return false;
+    }
+    if (roi.npixels() == 0 || roi.nchannels() < 1) {
+        reference.errorfmt("estimate_translation: empty region of interest");
+        return false;
+    }
+
+    int w  = roi.width();
+    int h  = roi.height();
+    int nc = roi.nchannels();
+    std::vector<float> R(roi.npixels() * nc), T(roi.npixels() * nc);
+    if (!reference.get_pixels(roi, TypeFloat, R.data())) {
+        reference.errorfmt("{}", reference.geterror());
+        return false;
+    }
+    if (!target.get_pixels(roi, TypeFloat, T.data())) {
+        reference.errorfmt("{}", target.geterror());
+        return false;
+    }
+    max_shift = std::min(max_shift, std::min(w, h) / 2);
+
+    // Build an image pyramid, halving the resolution until the remaining
+    // search range is small or the images would get too small to match.
+    struct Level {
+        std::vector<float> R, T;
+        int w, h;
+    };
+    std::vector<Level> levels;
+    levels.push_back({ std::move(R), std::move(T), w, h });
+    while ((max_shift >> (levels.size() - 1)) > 2
+           && std::min(levels.back().w, levels.back().h) / 2 >= 16) {
+        const Level& f(levels.back());
+        Level c;
+        c.R = translation_downsample(f.R, f.w, f.h, nc);
+        c.T = translation_downsample(f.T, f.w, f.h, nc);
+        c.w = f.w / 2;
+        c.h = f.h / 2;
+        levels.push_back(std::move(c));
+    }
+
+    // Search every shift within range at the coarsest level, then at each
+    // finer level, search +/- 2 pixels around the doubled best shift.
+    int bx = 0, by = 0;
+    for (int l = int(levels.size()) - 1; l >= 0; --l) {
+        const Level& lev(levels[l]);
+        int range  = (max_shift + (1 << l) - 1) >> l;
+        range      = std::min(range, std::min(lev.w, lev.h) / 2);
+        int radius = range;
+        if (l < int(levels.size()) - 1) {
+            bx *= 2;
+            by *= 2;
+            radius = 2;
+        }
+        int xmin = std::max(bx - radius, -range);
+        int xmax = std::min(bx + radius, range);
+        int ymin = std::max(by - radius, -range);
+        int ymax = std::min(by + radius, range);
+
+        int nx = xmax - xmin + 1, ny = ymax - ymin + 1;
+        std::vector<double> err(size_t(nx) * ny);
+        parallel_for(
+            0, nx * ny,
+            [&](int32_t i) {
+                err[i] = translation_error(lev.R, lev.T, lev.w, lev.h, nc,
+                                           xmin + i % nx, ymin + i / nx);
+            },
+            paropt(nthreads, paropt::SplitDir::Y, 1));
+        int best = 0;
+        for (int i = 1; i < nx * ny; ++i)
+            if (err[i] < err[best])
+                best = i;
+        bx = xmin + best % nx;
+        by = ymin + best / nx;
+    }
+    dx = float(bx);
+    dy = float(by);
+
+    // Sub-pixel refinement: the vertex of the parabola through the errors
+    // at the best shift and its two neighbors along each axis.
+    auto vertex = [](double em, double e0, double ep) {
+        double denom = em - 2.0 * e0 + ep;
+        return denom > 0.0 ? float(0.5 * (em - ep) / denom) : 0.0f;
+    };
+    const Level& base(levels[0]);
+    auto error = [&](int sx, int sy) {
+        return translation_error(base.R, base.T, w, h, nc, sx, sy);
+    };
+    double e0 = error(bx, by);
+    if (bx > -max_shift && bx < max_shift)
+        dx += vertex(error(bx - 1, by), e0, error(bx + 1, by));
+    if (by > -max_shift && by < max_shift)
+        dy += vertex(error(bx, by - 1), e0, error(bx, by + 1));
+    return true;
+}
+
+
+
 namespace {
 
 std::string
@@ -1000,6 +1460,205 @@ ImageBufAlgo::histogram(const ImageBuf& src, int channel, int bins, float min,
 
 
 
+template<class Atype>
+static bool
+gather_channel_values(const ImageBuf& src,
+                      std::vector<std::vector<float>>& values, ROI roi,
+                      int /*nthreads*/)
+{
+    for (ImageBuf::ConstIterator<Atype> a(src, roi); !a.done(); ++a) {
+        if (!a.exists())
+            continue;
+        for (int c = roi.chbegin; c < roi.chend; ++c) {
+            float v = a[c];
+            if (!isnan(v))
+                values[c - roi.chbegin].push_back(v);
+        }
+    }
+    return true;
+}
+
+
+
+std::vector<std::vector<float>>
+ImageBufAlgo::percentiles(const ImageBuf& src, cspan<float> percentiles,
+                          ROI roi, int nthreads)
+{
+    pvt::LoggedTimer logtimer("IBA::percentiles");
+    std::vector<std::vector<float>> result;
+    if (src.deep()) {
+        src.errorfmt("percentiles does not support deep images");
+        return result;
+    }
+    for (float p : percentiles) {
+        if (!(p >= 0.0f && p <= 100.0f)) {
+            src.errorfmt("Invalid percentile {}, must be in [0,100]", p);
+            return result;
+        }
+    }
+    if (!roi.defined())
+        roi = get_roi(src.spec());
+    roi.chend = std::min(roi.chend, src.nchannels());
+    if (roi.nchannels() < 1) {
+        src.errorfmt("Input image must have at least 1 channel");
+        return result;
+    }
+
+    // Gather all the values of each channel, so that we can find the exact
+    // order statistics.
+    std::vector<std::vector<float>> values(roi.nchannels());
+    for (auto& v : values)
+        v.reserve(roi.npixels());
+    bool ok = true;
+    OIIO_DISPATCH_TYPES(ok, "percentiles", gather_channel_values,
+                        src.spec().format, src, values, roi, nthreads);
+    if (!ok)
+        return result;
+
+    result.resize(roi.nchannels());
+    for (int c = 0; c < roi.nchannels(); ++c) {
+        auto& v = values[c];
+        std::sort(v.begin(), v.end());
+        result[c].resize(percentiles.size(), 0.0f);
+        if (v.empty())
+            continue;  // all NaN: leave the results as 0
+        for (size_t i = 0; i < percentiles.size(); ++i) {
+            float pos = percentiles[i] * 0.01f * float(v.size() - 1);
+            size_t lo = std::min(size_t(pos), v.size() - 1);
+            size_t hi = std::min(lo + 1, v.size() - 1);
+            result[c][i] = OIIO::lerp(v[lo], v[hi], pos - float(lo));
+        }
+    }
+    return result;
+}
+
+
+
+std::vector<std::vector<float>>
+ImageBufAlgo::palette(const ImageBuf& src, int k, ROI roi, int /*nthreads*/)
+{
+    pvt::LoggedTimer logtimer("IBA::palette");
+    std::vector<std::vector<float>> result;
+    if (src.deep()) {
+        src.errorfmt("palette does not support deep images");
+        return result;
+    }
+    if (k < 1) {
+        src.errorfmt("palette: k must be at least 1 (got {})", k);
+        return result;
+    }
+    if (!roi.defined())
+        roi = get_roi(src.spec());
+    roi.chend = std::min(roi.chend, src.nchannels());
+    if (roi.nchannels() < 1) {
+        src.errorfmt("Input image must have at least 1 channel");
+        return result;
+    }
+
+    // Grab all the pixel values, dropping any pixel that has a NaN
+    int nc = roi.nchannels();
+    std::vector<float> pixels(roi.npixels() * nc);
+    if (!src.get_pixels(roi, TypeFloat, pixels.data()))
+        return result;
+    size_t n = 0;
+    for (size_t i = 0, e = roi.npixels(); i < e; ++i) {
+        const float* p = &pixels[i * nc];
+        if (std::none_of(p, p + nc, [](float v) { return isnan(v); }))
+            std::copy(p, p + nc, &pixels[n++ * nc]);
+    }
+    if (n == 0)
+        return result;
+    auto dist2 = [&](const float* a, const float* b) {
+        float d2 = 0.0f;
+        for (int c = 0; c < nc; ++c)
+            d2 += (a[c] - b[c]) * (a[c] - b[c]);
+        return d2;
+    };
+
+    // Deterministic "farthest point" seeding: start with the pixel closest
+    // to the mean, then keep adding the pixel that is farthest from all the
+    // centers so far, until we have k of them or run out of distinct colors.
+    std::vector<float> centers(nc, 0.0f);
+    for (size_t i = 0; i < n; ++i)
+        for (int c = 0; c < nc; ++c)
+            centers[c] += pixels[i * nc + c] / float(n);
+    std::vector<float> mindist(n);
+    for (size_t i = 0; i < n; ++i)
+        mindist[i] = dist2(&pixels[i * nc], centers.data());
+    size_t best = 0;
+    for (size_t i = 1; i < n; ++i)
+        if (mindist[i] < mindist[best])
+            best = i;
+    centers.assign(&pixels[best * nc], &pixels[best * nc] + nc);
+    for (size_t i = 0; i < n; ++i)
+        mindist[i] = dist2(&pixels[i * nc], centers.data());
+    int ncenters = 1;
+    while (ncenters < k) {
+        best = 0;
+        for (size_t i = 1; i < n; ++i)
+            if (mindist[i] > mindist[best])
+                best = i;
+        if (mindist[best] <= 0.0f)
+            break;  // every pixel already coincides with a center
+        centers.insert(centers.end(), &pixels[best * nc],
+                       &pixels[best * nc] + nc);
+        const float* newcenter = &centers[ncenters * nc];
+        for (size_t i = 0; i < n; ++i)
+            mindist[i] = std::min(mindist[i],
+                                  dist2(&pixels[i * nc], newcenter));
+        ++ncenters;
+    }
+
+    // Lloyd iterations: assign each pixel to its nearest center, then move
+    // each center to the mean of its pixels, until nothing changes.
+    std::vector<int> label(n, -1);
+    std::vector<size_t> count(ncenters);
+    std::vector<double> sums(ncenters * nc);
+    for (int iter = 0; iter < 100; ++iter) {
+        bool changed = false;
+        for (size_t i = 0; i < n; ++i) {
+            int nearest = 0;
+            float d     = dist2(&pixels[i * nc], &centers[0]);
+            for (int j = 1; j < ncenters; ++j) {
+                float dj = dist2(&pixels[i * nc], &centers[j * nc]);
+                if (dj < d) {
+                    d       = dj;
+                    nearest = j;
+                }
+            }
+            changed |= (label[i] != nearest);
+            label[i] = nearest;
+        }
+        std::fill(count.begin(), count.end(), 0);
+        std::fill(sums.begin(), sums.end(), 0.0);
+        for (size_t i = 0; i < n; ++i) {
+            ++count[label[i]];
+            for (int c = 0; c < nc; ++c)
+                sums[label[i] * nc + c] += pixels[i * nc + c];
+        }
+        for (int j = 0; j < ncenters; ++j)
+            if (count[j])
+                for (int c = 0; c < nc; ++c)
+                    centers[j * nc + c] = float(sums[j * nc + c]
+                                                / double(count[j]));
+        if (!changed)
+            break;
+    }
+
+    // Sort the non-empty clusters by decreasing population
+    std::vector<int> order;
+    for (int j = 0; j < ncenters; ++j)
+        if (count[j])
+            order.push_back(j);
+    std::stable_sort(order.begin(), order.end(),
+                     [&](int a, int b) { return count[a] > count[b]; });
+    for (int j : order)
+        result.emplace_back(&centers[j * nc], &centers[j * nc] + nc);
+    return result;
+}
+
+
+
 /// histogram_impl -----------------------------------------------------------
 /// Fully type-specialized version of histogram.
 ///
@@ -3,8 +3,10 @@
 // https://github.com/AcademySoftwareFoundation/OpenImageIO
 
 
+#include <algorithm>
 #include <cmath>
 #include <iostream>
+#include <numeric>
 #include <stdexcept>
 
 #include <OpenImageIO/half.h>
@@ -23,11 +25,17 @@
 OIIO_NAMESPACE_BEGIN
 
 
-// FIXME -- NOT CORRECT!  This code assumes sorted, non-overlapping samples.
-// That is not a valid assumption in general. We will come back to fix this.
+static const ustring alpha_threshold_us("alpha_threshold");
+static const ustring sort_us("sort");
+
+
+// FIXME -- NOT CORRECT!  This code assumes non-overlapping samples, and
+// unless `sort` is true, that they are already sorted front to back. That
+// is not a valid assumption in general. We will come back to fix this.
 template<class DSTTYPE>
 static bool
-flatten_(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads)
+flatten_(ImageBuf& dst, const ImageBuf& src, float alpha_threshold, bool sort,
+         ROI roi, int nthreads)
 {
     ImageBufAlgo::parallel_image(roi, nthreads, [=, &dst, &src](ROI roi) {
         const ImageSpec& srcspec(src.spec());
@@ -45,20 +53,29 @@ flatten_(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads)
         float& ARval(val[AR_channel]);
         float& AGval(val[AG_channel]);
         float& ABval(val[AB_channel]);
+        std::vector<int> order;
 
         for (ImageBuf::Iterator<DSTTYPE> r(dst, roi); !r.done(); ++r) {
             int x = r.x(), y = r.y(), z = r.z();
             int samps = src.deep_samples(x, y, z);
+            // Composite order of the samples, front to back
+            order.resize(samps);
+            std::iota(order.begin(), order.end(), 0);
+            if (sort && Z_channel >= 0)
+                std::stable_sort(order.begin(), order.end(), [&](int a, int b) {
+                    return src.deep_value(x, y, z, Z_channel, a)
+                           < src.deep_value(x, y, z, Z_channel, b);
+                });
             // Clear accumulated values for this pixel (0 for colors, big for Z)
             memset(val, 0, nc * sizeof(float));
             if (Z_channel >= 0 && samps == 0)
                 val[Z_channel] = 1.0e30;
             if (Zback_channel >= 0 && samps == 0)
                 val[Zback_channel] = 1.0e30;
-            for (int s = 0; s < samps; ++s) {
+            for (int s : order) {
                 float AR = ARval, AG = AGval, AB = ABval;  // make copies
                 float alpha = (AR + AG + AB) / 3.0f;
-                if (alpha >= 1.0f)
+                if (alpha >= alpha_threshold)
                     break;
                 for (int c = 0; c < nc; ++c) {
                     float v = src.deep_value(x, y, z, c, s);
@@ -87,6 +104,15 @@ flatten_(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads)
 
 bool
 ImageBufAlgo::flatten(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads)
+{
+    return flatten(dst, src, KWArgs(), roi, nthreads);
+}
+
+
+
+bool
+ImageBufAlgo::flatten(ImageBuf& dst, const ImageBuf& src, KWArgs options,
+                      ROI roi, int nthreads)
 {
     pvt::LoggedTimer logtime("IBA::flatten");
     if (!src.deep()) {
@@ -114,9 +140,12 @@ ImageBufAlgo::flatten(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads)
         return false;
     }
 
+    float alpha_threshold = options.get_float(alpha_threshold_us, 1.0f);
+    bool sort             = options.get_int(sort_us, 0);
+
     bool ok;
     OIIO_DISPATCH_TYPES(ok, "flatten", flatten_, dst.spec().format, dst, src,
-                        roi, nthreads);
+                        alpha_threshold, sort, roi, nthreads);
     return ok;
 }
 
@@ -134,6 +163,19 @@ ImageBufAlgo::flatten(const ImageBuf& src, ROI roi, int nthreads)
 
 
 
+ImageBuf
+ImageBufAlgo::flatten(const ImageBuf& src, KWArgs options, ROI roi,
+                      int nthreads)
+{
+    ImageBuf result;
+    bool ok = flatten(result, src, options, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::flatten error");
+    return result;
+}
+
+
+
 bool
 ImageBufAlgo::deepen(ImageBuf& dst, const ImageBuf& src, float zvalue, ROI roi,
                      int /*nthreads*/)
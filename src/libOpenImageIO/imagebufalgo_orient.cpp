@@ -141,6 +141,87 @@ ImageBufAlgo::flop(const ImageBuf& src, ROI roi, int nthreads)
 
 
 
+template<class D, class S = D>
+static bool
+flip_axes_(ImageBuf& dst, const ImageBuf& src, bool flip_x, bool flip_y,
+           bool flip_z, ROI dst_roi, int /*nthreads*/)
+{
+    ROI src_roi_full = src.roi_full();
+    ROI dst_roi_full = dst.roi_full();
+    ImageBuf::ConstIterator<S, D> s(src);
+    ImageBuf::Iterator<D, D> d(dst, dst_roi);
+    for (; !d.done(); ++d) {
+        int x = flip_x ? src_roi_full.xend - 1 - (d.x() - dst_roi_full.xbegin)
+                       : d.x();
+        int y = flip_y ? src_roi_full.yend - 1 - (d.y() - dst_roi_full.ybegin)
+                       : d.y();
+        int z = flip_z ? src_roi_full.zend - 1 - (d.z() - dst_roi_full.zbegin)
+                       : d.z();
+        s.pos(x, y, z);
+        for (int c = dst_roi.chbegin; c < dst_roi.chend; ++c)
+            d[c] = s[c];
+    }
+    return true;
+}
+
+
+bool
+ImageBufAlgo::flip_axes(ImageBuf& dst, const ImageBuf& src, bool flip_x,
+                        bool flip_y, bool flip_z, ROI roi, int nthreads)
+{
+    if (&dst == &src) {  // Handle in-place operation
+        ImageBuf tmp;
+        tmp.swap(const_cast<ImageBuf&>(src));
+        return flip_axes(dst, tmp, flip_x, flip_y, flip_z, roi, nthreads);
+    }
+
+    pvt::LoggedTimer logtime("IBA::flip_axes");
+    ROI src_roi      = roi.defined() ? roi : src.roi();
+    ROI src_roi_full = src.roi_full();
+
+    // Compute the destination ROI, it's the source ROI reflected across
+    // the midplane of the display window along each of the flipped axes.
+    ROI dst_roi = src_roi;
+    if (flip_x) {
+        dst_roi.xbegin = src_roi_full.xbegin + src_roi_full.xend
+                         - src_roi.xend;
+        dst_roi.xend = dst_roi.xbegin + src_roi.width();
+    }
+    if (flip_y) {
+        dst_roi.ybegin = src_roi_full.ybegin + src_roi_full.yend
+                         - src_roi.yend;
+        dst_roi.yend = dst_roi.ybegin + src_roi.height();
+    }
+    if (flip_z) {
+        dst_roi.zbegin = src_roi_full.zbegin + src_roi_full.zend
+                         - src_roi.zend;
+        dst_roi.zend = dst_roi.zbegin + src_roi.depth();
+    }
+
+    if (!IBAprep(dst_roi, &dst, &src))
+        return false;
+    bool ok;
+    OIIO_DISPATCH_COMMON_TYPES2(ok, "flip_axes", flip_axes_, dst.spec().format,
+                                src.spec().format, dst, src, flip_x, flip_y,
+                                flip_z, dst_roi, nthreads);
+    return ok;
+}
+
+
+
+ImageBuf
+ImageBufAlgo::flip_axes(const ImageBuf& src, bool flip_x, bool flip_y,
+                        bool flip_z, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = flip_axes(result, src, flip_x, flip_y, flip_z, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::flip_axes() error");
+    return result;
+}
+
+
+
 template<class D, class S = D>
 static bool
 rotate90_(ImageBuf& dst, const ImageBuf& src, ROI dst_roi, int /*nthreads*/)
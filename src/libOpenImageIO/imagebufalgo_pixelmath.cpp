@@ -6,6 +6,7 @@
 /// Implementation of ImageBufAlgo algorithms that do math on
 /// single pixels at a time.
 
+#include <algorithm>
 #include <cmath>
 #include <iostream>
 #include <limits>
@@ -1113,6 +1114,137 @@ ImageBufAlgo::contrast_remap(const ImageBuf& src, cspan<float> black,
 
 
 
+// Evaluate the monotone cubic Hermite curve with knots (px, py) and
+// tangents m at x, holding the end values outside the knot range.
+static float
+eval_monotone_curve(float x, cspan<float> px, cspan<float> py,
+                    cspan<float> m)
+{
+    size_t n = px.size();
+    if (std::isnan(x))
+        return x;
+    if (x <= px[0])
+        return py[0];
+    if (x >= px[n - 1])
+        return py[n - 1];
+    auto it   = std::upper_bound(px.begin(), px.end(), x);
+    size_t k  = size_t(it - px.begin()) - 1;
+    float h   = px[k + 1] - px[k];
+    float t   = (x - px[k]) / h;
+    float t2  = t * t;
+    float t3  = t2 * t;
+    float h00 = 2.0f * t3 - 3.0f * t2 + 1.0f;
+    float h10 = t3 - 2.0f * t2 + t;
+    float h01 = 3.0f * t2 - 2.0f * t3;
+    float h11 = t3 - t2;
+    return h00 * py[k] + h10 * h * m[k] + h01 * py[k + 1]
+           + h11 * h * m[k + 1];
+}
+
+
+
+template<class D, class S>
+static bool
+curve_remap_(ImageBuf& dst, const ImageBuf& src, cspan<float> px,
+             cspan<float> py, cspan<float> m, int channel, ROI roi,
+             int nthreads)
+{
+    ImageBufAlgo::parallel_image(roi, nthreads, [&](ROI roi) {
+        ImageBuf::ConstIterator<S> s(src, roi);
+        for (ImageBuf::Iterator<D> d(dst, roi); !d.done(); ++d, ++s) {
+            for (int c = roi.chbegin; c < roi.chend; ++c) {
+                if (channel < 0 || c == channel)
+                    d[c] = eval_monotone_curve(s[c], px, py, m);
+                else
+                    d[c] = s[c];
+            }
+        }
+    });
+    return true;
+}
+
+
+
+bool
+ImageBufAlgo::curve_remap(ImageBuf& dst, const ImageBuf& src,
+                          cspan<float> points, int channel, ROI roi,
+                          int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::curve_remap");
+    size_t n = points.size() / 2;
+    if (points.size() % 2 || n < 2) {
+        dst.errorfmt(
+            "curve_remap: need at least two (x,y) control points, got {} values",
+            points.size());
+        return false;
+    }
+    std::vector<float> px(n), py(n);
+    for (size_t i = 0; i < n; ++i) {
+        px[i] = points[2 * i];
+        py[i] = points[2 * i + 1];
+        if (i && !(px[i] > px[i - 1])) {
+            dst.errorfmt(
+                "curve_remap: control point inputs must be strictly increasing");
+            return false;
+        }
+    }
+    if (!IBAprep(roi, &dst, &src))
+        return false;
+    if (channel < -1 || channel >= src.nchannels()) {
+        dst.errorfmt("curve_remap: invalid channel {}", channel);
+        return false;
+    }
+
+    // Fritsch-Carlson tangents: start from the averaged secant slopes
+    // (zero at local extrema), then shrink any pair of tangents that would
+    // let a segment overshoot.
+    std::vector<float> delta(n - 1), m(n);
+    for (size_t k = 0; k < n - 1; ++k)
+        delta[k] = (py[k + 1] - py[k]) / (px[k + 1] - px[k]);
+    m[0]     = delta[0];
+    m[n - 1] = delta[n - 2];
+    for (size_t k = 1; k < n - 1; ++k)
+        m[k] = (delta[k - 1] * delta[k] <= 0.0f)
+                   ? 0.0f
+                   : 0.5f * (delta[k - 1] + delta[k]);
+    for (size_t k = 0; k < n - 1; ++k) {
+        if (delta[k] == 0.0f) {
+            m[k]     = 0.0f;
+            m[k + 1] = 0.0f;
+            continue;
+        }
+        float a = m[k] / delta[k];
+        float b = m[k + 1] / delta[k];
+        float s = a * a + b * b;
+        if (s > 9.0f) {
+            float tau = 3.0f / sqrtf(s);
+            m[k]      = tau * a * delta[k];
+            m[k + 1]  = tau * b * delta[k];
+        }
+    }
+
+    bool ok;
+    OIIO_DISPATCH_COMMON_TYPES2(ok, "curve_remap", curve_remap_,
+                                dst.spec().format, src.spec().format, dst, src,
+                                px, py, m, channel, roi, nthreads);
+    return ok;
+}
+
+
+
+ImageBuf
+ImageBufAlgo::curve_remap(const ImageBuf& src, cspan<float> points,
+                          int channel, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = curve_remap(result, src, points, channel, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::curve_remap error");
+    return result;
+}
+
+
+
 template<class Rtype, class Atype>
 static bool
 saturate_(ImageBuf& R, const ImageBuf& A, float scale, int firstchannel,
@@ -1199,6 +1331,331 @@ ImageBufAlgo::saturate(const ImageBuf& src, float scale, int firstchannel,
 
 
 
+template<class Rtype, class Atype>
+static bool
+tonemap_(ImageBuf& R, const ImageBuf& A, int method, float scale,
+         float whitepoint, ROI roi, int nthreads)
+{
+    ImageBufAlgo::parallel_image(roi, nthreads, [&](ROI roi) {
+        int alpha_channel = A.spec().alpha_channel;
+        int z_channel     = A.spec().z_channel;
+        float invw2       = 1.0f / (whitepoint * whitepoint);
+        ImageBuf::ConstIterator<Atype> a(A, roi);
+        for (ImageBuf::Iterator<Rtype> r(R, roi); !r.done(); ++r, ++a) {
+            for (int c = roi.chbegin; c < roi.chend; ++c) {
+                if (c == alpha_channel || c == z_channel) {
+                    r[c] = a[c];
+                    continue;
+                }
+                float x = std::max(a[c] * scale, 0.0f);
+                if (method == 0) {
+                    // Reinhard
+                    x = x / (1.0f + x);
+                } else if (method == 1) {
+                    // Extended Reinhard
+                    x = x * (1.0f + x * invw2) / (1.0f + x);
+                } else {
+                    // Narkowicz's fit of the ACES filmic curve
+                    x = (x * (2.51f * x + 0.03f))
+                        / (x * (2.43f * x + 0.59f) + 0.14f);
+                    x = OIIO::clamp(x, 0.0f, 1.0f);
+                }
+                r[c] = x;
+            }
+        }
+    });
+    return true;
+}
+
+
+
+bool
+ImageBufAlgo::tonemap(ImageBuf& dst, const ImageBuf& src, string_view method,
+                      float exposure, float whitepoint, ROI roi, int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::tonemap");
+    int m = -1;
+    if (method == "reinhard")
+        m = 0;
+    else if (method == "reinhard_extended")
+        m = 1;
+    else if (method == "aces")
+        m = 2;
+    if (m < 0) {
+        dst.errorfmt("ImageBufAlgo::tonemap: unknown method \"{}\"", method);
+        return false;
+    }
+    if (!IBAprep(roi, &dst, &src, IBAprep_CLAMP_MUTUAL_NCHANNELS))
+        return false;
+
+    float scale = std::exp2(exposure);
+    if (m == 1 && whitepoint <= 0.0f) {
+        // No white point given -- use the brightest color value in the ROI
+        PixelStats stats = computePixelStats(src, roi, nthreads);
+        if (stats.max.empty()) {
+            dst.errorfmt("ImageBufAlgo::tonemap: could not compute white point");
+            return false;
+        }
+        whitepoint = 0.0f;
+        for (int c = roi.chbegin; c < roi.chend; ++c)
+            if (c != src.spec().alpha_channel && c != src.spec().z_channel)
+                whitepoint = std::max(whitepoint, stats.max[c]);
+        whitepoint *= scale;
+        if (!(whitepoint > 0.0f))
+            whitepoint = 1.0f;
+    }
+
+    bool ok = true;
+    OIIO_DISPATCH_COMMON_TYPES2(ok, "tonemap", tonemap_, dst.spec().format,
+                                src.spec().format, dst, src, m, scale,
+                                whitepoint, roi, nthreads);
+    return ok;
+}
+
+
+
+ImageBuf
+ImageBufAlgo::tonemap(const ImageBuf& src, string_view method, float exposure,
+                      float whitepoint, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = tonemap(result, src, method, exposure, whitepoint, roi,
+                      nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::tonemap() error");
+    return result;
+}
+
+
+
+// Linear Rec709 RGB of a blackbody radiator at temperature T (Kelvin),
+// normalized to Y = 1. The chromaticity uses the cubic spline
+// approximation of the Planckian locus by Kim et al. (2002).
+static void
+blackbody_rgb(float T, float rgb[3])
+{
+    T = OIIO::clamp(T, 2000.0f, 25000.0f);
+    // Powers of 1000/T, so the coefficients appear in their usual form.
+    double t  = 1000.0 / T;
+    double t2 = t * t;
+    double t3 = t2 * t;
+    double x;
+    if (T <= 4000.0f)
+        x = -0.2661239 * t3 - 0.2343589 * t2 + 0.8776956 * t + 0.179910;
+    else
+        x = -3.0258469 * t3 + 2.1070379 * t2 + 0.2226347 * t + 0.240390;
+    double x2 = x * x;
+    double x3 = x2 * x;
+    double y;
+    if (T <= 2222.0f)
+        y = -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683;
+    else if (T <= 4000.0f)
+        y = -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867;
+    else
+        y = 3.0817580 * x3 - 5.87338670 * x2 + 3.75112997 * x - 0.37001483;
+    // xyY -> XYZ -> linear Rec709
+    double X = x / y;
+    double Z = (1.0 - x - y) / y;
+    rgb[0]   = float(3.2404542 * X - 1.5371385 - 0.4985314 * Z);
+    rgb[1]   = float(-0.9692660 * X + 1.8760108 + 0.0415560 * Z);
+    rgb[2]   = float(0.0556434 * X - 0.2040259 + 1.0572252 * Z);
+}
+
+
+
+bool
+ImageBufAlgo::white_balance(ImageBuf& dst, const ImageBuf& src,
+                            float temperature, float tint, ROI roi,
+                            int nthreads)
+{
+    // N.B.: Don't log time, it's just a mul.
+    if (src.nchannels() < 3) {
+        dst.errorfmt("ImageBufAlgo::white_balance: requires at least 3 channels");
+        return false;
+    }
+    float rgb[3], ref[3];
+    blackbody_rgb(temperature, rgb);
+    blackbody_rgb(6500.0f, ref);
+    std::vector<float> gains(src.nchannels(), 1.0f);
+    for (int c = 0; c < 3; ++c)
+        gains[c] = rgb[c] / ref[c];
+    gains[1] *= std::exp2(-tint);
+    // Preserve the luminance of neutral colors
+    float luma = 0.2126f * gains[0] + 0.7152f * gains[1] + 0.0722f * gains[2];
+    for (int c = 0; c < 3; ++c)
+        gains[c] /= luma;
+    return mul(dst, src, gains, roi, nthreads);
+}
+
+
+
+ImageBuf
+ImageBufAlgo::white_balance(const ImageBuf& src, float temperature,
+                            float tint, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = white_balance(result, src, temperature, tint, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::white_balance() error");
+    return result;
+}
+
+
+
+template<class Rtype, class Atype>
+static bool
+vignette_(ImageBuf& R, const ImageBuf& A, float amount, float radius,
+          float roundness, ROI roi, int nthreads)
+{
+    // Center and half-extents of the display window, with the extents
+    // blended between the window's aspect and a circle.
+    const ImageSpec& spec = A.spec();
+    float cx              = spec.full_x + 0.5f * spec.full_width;
+    float cy              = spec.full_y + 0.5f * spec.full_height;
+    float hw              = std::max(0.5f * spec.full_width, 0.5f);
+    float hh              = std::max(0.5f * spec.full_height, 0.5f);
+    float circle          = std::sqrt(hw * hh);
+    float ex              = OIIO::lerp(hw, circle, roundness);
+    float ey              = OIIO::lerp(hh, circle, roundness);
+    // Scale so that the corners are at distance 1
+    float dscale = 1.0f / std::hypot(hw / ex, hh / ey);
+    float range  = std::max(1.0f - radius, 1.0e-6f);
+
+    ImageBufAlgo::parallel_image(roi, nthreads, [&](ROI roi) {
+        int alpha_channel = spec.alpha_channel;
+        int z_channel     = spec.z_channel;
+        ImageBuf::ConstIterator<Atype> a(A, roi);
+        for (ImageBuf::Iterator<Rtype> r(R, roi); !r.done(); ++r, ++a) {
+            float d = dscale
+                      * std::hypot((r.x() + 0.5f - cx) / ex,
+                                   (r.y() + 0.5f - cy) / ey);
+            float t = OIIO::clamp((d - radius) / range, 0.0f, 1.0f);
+            float f = 1.0f - amount * t * t * (3.0f - 2.0f * t);
+            for (int c = roi.chbegin; c < roi.chend; ++c) {
+                if (c == alpha_channel || c == z_channel)
+                    r[c] = a[c];
+                else
+                    r[c] = a[c] * f;
+            }
+        }
+    });
+    return true;
+}
+
+
+
+bool
+ImageBufAlgo::vignette(ImageBuf& dst, const ImageBuf& src, float amount,
+                       float radius, float roundness, ROI roi, int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::vignette");
+    if (!IBAprep(roi, &dst, &src, IBAprep_CLAMP_MUTUAL_NCHANNELS))
+        return false;
+    roundness = OIIO::clamp(roundness, 0.0f, 1.0f);
+    bool ok   = true;
+    OIIO_DISPATCH_COMMON_TYPES2(ok, "vignette", vignette_, dst.spec().format,
+                                src.spec().format, dst, src, amount, radius,
+                                roundness, roi, nthreads);
+    return ok;
+}
+
+
+
+ImageBuf
+ImageBufAlgo::vignette(const ImageBuf& src, float amount, float radius,
+                       float roundness, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = vignette(result, src, amount, radius, roundness, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::vignette() error");
+    return result;
+}
+
+
+
+bool
+ImageBufAlgo::film_grain(ImageBuf& dst, const ImageBuf& src, float intensity,
+                         float size, int seed, bool mono, ROI roi,
+                         int nthreads)
+{
+    // N.B.: Don't log time, it will be caught by the constituent parts
+    if (!IBAprep(roi, &dst, &src, IBAprep_NO_SUPPORT_VOLUME))
+        return false;
+
+    // Blurring reduces the variance of the noise by the sum of the squared
+    // kernel weights, so we compensate for that when scaling the grain.
+    ImageBuf kernel;
+    float stddev = 1.0f;
+    ROI noiseroi = roi;
+    if (size > 0.0f) {
+        kernel = make_kernel("gaussian", size, size);
+        if (kernel.has_error()) {
+            dst.errorfmt("{}", kernel.geterror());
+            return false;
+        }
+        double sumsq = 0.0;
+        for (ImageBuf::ConstIterator<float> k(kernel); !k.done(); ++k)
+            sumsq += double(k[0]) * double(k[0]);
+        stddev = float(std::sqrt(sumsq));
+        // Generate the noise past the edges of the ROI by the kernel
+        // radius, so the grain doesn't fade out near the ROI boundary.
+        const ImageSpec& kspec(kernel.spec());
+        int rx = std::max(-kspec.x, kspec.x + kspec.width - 1);
+        int ry = std::max(-kspec.y, kspec.y + kspec.height - 1);
+        noiseroi.xbegin -= rx;
+        noiseroi.xend += rx;
+        noiseroi.ybegin -= ry;
+        noiseroi.yend += ry;
+    }
+
+    // Unit Gaussian noise covering the (padded) ROI
+    ImageSpec grainspec(noiseroi.width(), noiseroi.height(), src.nchannels(),
+                        TypeDesc::FLOAT);
+    grainspec.x = noiseroi.xbegin;
+    grainspec.y = noiseroi.ybegin;
+    ImageBuf grain(grainspec);
+    if (!noise(grain, "gaussian", 0.0f, 1.0f, mono, seed, noiseroi,
+               nthreads)) {
+        dst.errorfmt("{}", grain.geterror());
+        return false;
+    }
+    if (size > 0.0f) {
+        // Only the ROI part of the blurred grain is kept
+        grain = convolve(grain, kernel, true, roi, nthreads);
+        if (grain.has_error()) {
+            dst.errorfmt("{}", grain.geterror());
+            return false;
+        }
+    }
+    std::vector<float> scale(src.nchannels(), intensity / stddev);
+    if (src.spec().alpha_channel >= 0)
+        scale[src.spec().alpha_channel] = 0.0f;
+    if (src.spec().z_channel >= 0)
+        scale[src.spec().z_channel] = 0.0f;
+    if (!mul(grain, grain, scale, roi, nthreads)) {
+        dst.errorfmt("{}", grain.geterror());
+        return false;
+    }
+    return add(dst, src, grain, roi, nthreads);
+}
+
+
+
+ImageBuf
+ImageBufAlgo::film_grain(const ImageBuf& src, float intensity, float size,
+                         int seed, bool mono, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = film_grain(result, src, intensity, size, seed, mono, roi,
+                         nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::film_grain() error");
+    return result;
+}
+
+
+
 template<class D, class S>
 static bool
 color_map_(ImageBuf& dst, const ImageBuf& src, int srcchannel, int nknots,
@@ -1819,4 +2276,102 @@ ImageBufAlgo::zover(const ImageBuf& A, const ImageBuf& B, bool z_zeroisinf,
 }
 
 
+
+template<class D, class S>
+static bool
+chroma_key_(ImageBuf& dst, const ImageBuf& src, const float* key,
+            float tolerance, float softness, bool despill, ROI roi,
+            int nthreads)
+{
+    // Despill acts on the channel in which the key color is strongest
+    // (green, unless red or blue clearly dominates).
+    int k = 1;
+    if (key[0] > key[1] && key[0] >= key[2])
+        k = 0;
+    else if (key[2] > key[1] && key[2] > key[0])
+        k = 2;
+    int o1 = (k + 1) % 3, o2 = (k + 2) % 3;
+    ImageBufAlgo::parallel_image(roi, nthreads, [&](ROI roi) {
+        int alpha_channel = src.spec().alpha_channel;
+        ImageBuf::ConstIterator<S> s(src, roi);
+        for (ImageBuf::Iterator<D> d(dst, roi); !d.done(); ++d, ++s) {
+            float rgb[3] = { s[0], s[1], s[2] };
+            float d2     = 0.0f;
+            for (int c = 0; c < 3; ++c)
+                d2 += (rgb[c] - key[c]) * (rgb[c] - key[c]);
+            float dist = sqrtf(d2);
+            float a;
+            if (softness > 0.0f) {
+                a = OIIO::clamp((dist - tolerance) / softness, 0.0f, 1.0f);
+                a = a * a * (3.0f - 2.0f * a);
+            } else {
+                a = dist > tolerance ? 1.0f : 0.0f;
+            }
+            if (despill)
+                rgb[k] = std::min(rgb[k], std::max(rgb[o1], rgb[o2]));
+            for (int c = 0; c < 3; ++c)
+                d[c] = rgb[c] * a;
+            d[3] = alpha_channel >= 0 ? s[alpha_channel] * a : a;
+        }
+    });
+    return true;
+}
+
+
+
+bool
+ImageBufAlgo::chroma_key(ImageBuf& dst, const ImageBuf& src,
+                         cspan<float> keycolor, float tolerance,
+                         float softness, bool despill, ROI roi, int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::chroma_key");
+    if (keycolor.size() < 3) {
+        dst.errorfmt("chroma_key: keycolor needs 3 values, got {}",
+                     keycolor.size());
+        return false;
+    }
+    if (tolerance < 0.0f || softness < 0.0f) {
+        dst.errorfmt("chroma_key: tolerance and softness may not be negative");
+        return false;
+    }
+    // The result is RGBA, regardless of the channels of src, but otherwise
+    // has the same data and display windows
+    ImageSpec spec = src.spec();
+    spec.nchannels = 4;
+    spec.default_channel_names();
+    spec.alpha_channel = 3;
+    spec.channelformats.clear();
+    if (!IBAprep(roi, &dst, &src, nullptr, &spec, IBAprep_NO_SUPPORT_VOLUME))
+        return false;
+    if (src.nchannels() < 3 || dst.nchannels() < 4) {
+        dst.errorfmt("chroma_key: needs an RGB source and an RGBA result");
+        return false;
+    }
+    roi.chbegin = 0;
+    roi.chend   = 4;
+
+    bool ok;
+    OIIO_DISPATCH_COMMON_TYPES2(ok, "chroma_key", chroma_key_,
+                                dst.spec().format, src.spec().format, dst, src,
+                                keycolor.data(), tolerance, softness, despill,
+                                roi, nthreads);
+    return ok;
+}
+
+
+
+ImageBuf
+ImageBufAlgo::chroma_key(const ImageBuf& src, cspan<float> keycolor,
+                         float tolerance, float softness, bool despill,
+                         ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = chroma_key(result, src, keycolor, tolerance, softness, despill,
+                         roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::chroma_key() error");
+    return result;
+}
+
+
 OIIO_NAMESPACE_END
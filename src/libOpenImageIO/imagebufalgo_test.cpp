@@ -6,6 +6,7 @@
 #include <cstdio>
 #include <iomanip>
 #include <iostream>
+#include <limits>
 #include <string>
 
 #include <OpenImageIO/platform.h>
@@ -21,6 +22,7 @@
 #include <OpenImageIO/argparse.h>
 #include <OpenImageIO/benchmark.h>
 #include <OpenImageIO/color.h>
+#include <OpenImageIO/filesystem.h>
 #include <OpenImageIO/imagebuf.h>
 #include <OpenImageIO/imagebufalgo.h>
 #include <OpenImageIO/imagebufalgo_util.h>
@@ -368,6 +370,76 @@ test_paste()
 
 
 
+// Tests ImageBufAlgo::flip_axes
+void
+test_flip_axes()
+{
+    std::cout << "test flip_axes\n";
+    // Make a 4x3x2 volume whose pixel values encode their coordinates
+    ImageSpec spec(4, 3, 3, TypeDesc::FLOAT);
+    spec.depth = spec.full_depth = 2;
+    ImageBuf A(spec);
+    for (ImageBuf::Iterator<float> it(A); !it.done(); ++it) {
+        it[0] = float(it.x());
+        it[1] = float(it.y());
+        it[2] = float(it.z());
+    }
+
+    // Flipping nothing is a copy
+    ImageBuf R = ImageBufAlgo::flip_axes(A, false, false, false);
+    OIIO_CHECK_EQUAL(ImageBufAlgo::compare(R, A, 0.0f, 0.0f).nfail, 0);
+
+    // Flipping x and y is the same as rotate180
+    R = ImageBufAlgo::flip_axes(A, true, true);
+    OIIO_CHECK_EQUAL(
+        ImageBufAlgo::compare(R, ImageBufAlgo::rotate180(A), 0.0f, 0.0f).nfail,
+        0);
+
+    // Flipping z reverses the slices
+    R = ImageBufAlgo::flip_axes(A, false, false, true);
+    for (ImageBuf::ConstIterator<float> r(R); !r.done(); ++r) {
+        OIIO_CHECK_EQUAL(r[0], float(r.x()));
+        OIIO_CHECK_EQUAL(r[1], float(r.y()));
+        OIIO_CHECK_EQUAL(r[2], float(1 - r.z()));
+    }
+}
+
+
+
+// Tests ImageBufAlgo::channels_by_name
+void
+test_channels_by_name()
+{
+    std::cout << "test channels_by_name\n";
+    ImageSpec spec(2, 2, 4, TypeDesc::FLOAT);
+    spec.channelnames = { "B", "G", "R", "A" };
+    const float BGRA[] = { 0.25f, 0.5f, 0.75f, 1.0f };
+    ImageBuf src       = filled_image(BGRA, spec);
+
+    std::string rgb[] = { "R", "G", "B" };
+    ImageBuf R        = ImageBufAlgo::channels_by_name(src, rgb);
+    OIIO_CHECK_EQUAL(R.nchannels(), 3);
+    OIIO_CHECK_EQUAL(R.spec().channel_name(0), "R");
+    OIIO_CHECK_EQUAL(R.spec().channel_name(2), "B");
+    OIIO_CHECK_EQUAL(R.getchannel(1, 1, 0, 0), 0.75f);
+    OIIO_CHECK_EQUAL(R.getchannel(1, 1, 0, 1), 0.5f);
+    OIIO_CHECK_EQUAL(R.getchannel(1, 1, 0, 2), 0.25f);
+
+    // Missing channel is an error unless filled
+    std::string rgbz[] = { "R", "G", "B", "Z" };
+    R                  = ImageBufAlgo::channels_by_name(src, rgbz);
+    OIIO_CHECK_ASSERT(R.has_error());
+    R = ImageBufAlgo::channels_by_name(src, rgbz, true, 0.0f);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.nchannels(), 4);
+    OIIO_CHECK_EQUAL(R.spec().channel_name(3), "Z");
+    OIIO_CHECK_EQUAL(R.spec().z_channel, 3);
+    OIIO_CHECK_EQUAL(R.getchannel(1, 1, 0, 0), 0.75f);
+    OIIO_CHECK_EQUAL(R.getchannel(1, 1, 0, 3), 0.0f);
+}
+
+
+
 void
 test_channel_append()
 {
@@ -559,6 +631,640 @@ test_max()
 
 
 
+// Tests ImageBufAlgo::curve_remap
+void
+test_curve_remap()
+{
+    std::cout << "test curve_remap\n";
+    // Each channel holds a different input value
+    const float Aval[]   = { 0.0f, 0.25f, 0.5f, 1.0f };
+    ImageBuf A           = filled_image(Aval);
+    const float points[] = { 0.0f, 0.0f, 0.5f, 0.25f, 1.0f, 1.0f };
+
+    // Endpoints are preserved, the midtone lands on its control point, and
+    // the curve stays monotone in between.
+    ImageBuf R = ImageBufAlgo::curve_remap(A, points);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(1, 1, 0, 0), 0.0f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(1, 1, 0, 2), 0.25f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(1, 1, 0, 3), 1.0f, 1e-6f);
+    OIIO_CHECK_GE(R.getchannel(1, 1, 0, 1), 0.0f);
+    OIIO_CHECK_LE(R.getchannel(1, 1, 0, 1), 0.25f);
+
+    // Only remap one channel
+    R = ImageBufAlgo::curve_remap(A, points, 2);
+    OIIO_CHECK_EQUAL(R.getchannel(1, 1, 0, 1), 0.25f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(1, 1, 0, 2), 0.25f, 1e-6f);
+
+    // Control point inputs must increase
+    const float badpoints[] = { 0.0f, 0.0f, 0.5f, 0.5f, 0.5f, 1.0f };
+    OIIO_CHECK_ASSERT(ImageBufAlgo::curve_remap(A, badpoints).has_error());
+
+    // The channel must exist
+    OIIO_CHECK_ASSERT(ImageBufAlgo::curve_remap(A, points, 4).has_error());
+    OIIO_CHECK_ASSERT(ImageBufAlgo::curve_remap(A, points, -2).has_error());
+}
+
+
+
+// Tests ImageBufAlgo::tonemap
+void
+test_tonemap()
+{
+    std::cout << "test tonemap\n";
+
+    // RGBA image with HDR color values; alpha should pass through
+    const float Aval[] = { 10.0f, 50.0f, 100.0f, 0.5f };
+    ImageBuf A         = filled_image(Aval);
+
+    for (auto method : { "reinhard", "reinhard_extended", "aces" }) {
+        ImageBuf R = ImageBufAlgo::tonemap(A, method);
+        OIIO_CHECK_ASSERT(!R.has_error());
+        auto stats = ImageBufAlgo::computePixelStats(R);
+        for (int c = 0; c < 3; ++c) {
+            OIIO_CHECK_GE(stats.min[c], 0.0f);
+            OIIO_CHECK_LE(stats.max[c], 1.0f);
+        }
+        OIIO_CHECK_EQUAL(stats.max[3], 0.5f);
+    }
+
+    // Simple Reinhard is x/(1+x)
+    ImageBuf R = ImageBufAlgo::tonemap(A, "reinhard");
+    for (int c = 0; c < 3; ++c)
+        OIIO_CHECK_EQUAL_THRESH(R.getchannel(1, 1, 0, c),
+                                Aval[c] / (1.0f + Aval[c]), 1e-6f);
+
+    // Extended Reinhard with automatic white point maps the max to 1
+    R = ImageBufAlgo::tonemap(A, "reinhard_extended");
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(1, 1, 0, 2), 1.0f, 1e-5f);
+
+    // One stop of exposure is the same as doubling the input
+    const float Bval[] = { 20.0f, 100.0f, 200.0f, 0.5f };
+    ImageBuf B         = filled_image(Bval);
+    auto comp = ImageBufAlgo::compare(ImageBufAlgo::tonemap(A, "aces", 1.0f),
+                                      ImageBufAlgo::tonemap(B, "aces"), 1e-6f,
+                                      1e-6f);
+    OIIO_CHECK_EQUAL(comp.nfail, 0);
+
+    // Unknown method is an error
+    R = ImageBufAlgo::tonemap(A, "bogus");
+    OIIO_CHECK_ASSERT(R.has_error());
+}
+
+
+
+// Tests ImageBufAlgo::white_balance
+void
+test_white_balance()
+{
+    std::cout << "test white_balance\n";
+    const float grey[4] = { 0.5f, 0.5f, 0.5f, 0.75f };
+    ImageBuf A          = filled_image(grey);
+
+    // 6500K with no tint is a no-op
+    ImageBuf R = ImageBufAlgo::white_balance(A, 6500.0f, 0.0f);
+    auto comp  = ImageBufAlgo::compare(R, A, 1e-5f, 1e-5f);
+    OIIO_CHECK_EQUAL(comp.nfail, 0);
+
+    // Lower temperature is warmer: more red relative to blue. Alpha is
+    // untouched, and neutral luminance is preserved.
+    R = ImageBufAlgo::white_balance(A, 4000.0f, 0.0f);
+    float pixel[4];
+    R.getpixel(1, 1, pixel);
+    OIIO_CHECK_GE(pixel[0] / pixel[2], 1.2f);
+    OIIO_CHECK_EQUAL(pixel[3], 0.75f);
+    OIIO_CHECK_EQUAL_THRESH(0.2126f * pixel[0] + 0.7152f * pixel[1]
+                                + 0.0722f * pixel[2],
+                            0.5f, 1e-5f);
+
+    // Higher temperature is cooler
+    R = ImageBufAlgo::white_balance(A, 10000.0f, 0.0f);
+    R.getpixel(1, 1, pixel);
+    OIIO_CHECK_LE(pixel[0] / pixel[2], 0.9f);
+
+    // Positive tint shifts toward magenta (less green)
+    R = ImageBufAlgo::white_balance(A, 6500.0f, 0.5f);
+    R.getpixel(1, 1, pixel);
+    OIIO_CHECK_LE(pixel[1], pixel[0]);
+    OIIO_CHECK_EQUAL_THRESH(pixel[0], pixel[2], 1e-5f);
+
+    // Too few channels is an error
+    const float one[1] = { 0.5f };
+    R = ImageBufAlgo::white_balance(filled_image(one), 5000.0f);
+    OIIO_CHECK_ASSERT(R.has_error());
+}
+
+
+
+// Tests ImageBufAlgo::vignette
+void
+test_vignette()
+{
+    std::cout << "test vignette\n";
+    const float grey[4] = { 0.5f, 0.5f, 0.5f, 1.0f };
+    ImageBuf A          = filled_image(grey, 16, 8);
+
+    // Zero amount is a no-op
+    ImageBuf R = ImageBufAlgo::vignette(A, 0.0f);
+    auto comp  = ImageBufAlgo::compare(R, A, 0.0f, 0.0f);
+    OIIO_CHECK_EQUAL(comp.nfail, 0);
+
+    // The center is untouched, the corners are darkened, alpha is not
+    R = ImageBufAlgo::vignette(A, 0.8f, 0.25f, 1.0f);
+    OIIO_CHECK_EQUAL(R.getchannel(8, 4, 0, 0), 0.5f);
+    OIIO_CHECK_LE(R.getchannel(0, 0, 0, 0), 0.2f);
+    OIIO_CHECK_LE(R.getchannel(15, 7, 0, 2), 0.2f);
+    OIIO_CHECK_LE(R.getchannel(0, 4, 0, 0), R.getchannel(4, 4, 0, 0));
+    OIIO_CHECK_EQUAL(R.getchannel(0, 0, 0, 3), 1.0f);
+}
+
+
+
+// Tests ImageBufAlgo::film_grain
+void
+test_film_grain()
+{
+    std::cout << "test film_grain\n";
+    const float grey[4] = { 0.5f, 0.5f, 0.5f, 1.0f };
+    ImageBuf A          = filled_image(grey, 64, 64);
+
+    // Zero intensity is a no-op
+    ImageBuf R = ImageBufAlgo::film_grain(A, 0.0f);
+    auto comp  = ImageBufAlgo::compare(R, A, 0.0f, 0.0f);
+    OIIO_CHECK_EQUAL(comp.nfail, 0);
+
+    // The grain has about the requested strength, and leaves alpha alone
+    R          = ImageBufAlgo::film_grain(A, 0.05f, 2.0f, 1);
+    auto stats = ImageBufAlgo::computePixelStats(R);
+    OIIO_CHECK_EQUAL_THRESH(stats.avg[0], 0.5f, 0.02f);
+    OIIO_CHECK_GE(stats.stddev[0], 0.025f);
+    OIIO_CHECK_LE(stats.stddev[0], 0.1f);
+    OIIO_CHECK_EQUAL(stats.min[3], 1.0f);
+    OIIO_CHECK_EQUAL(stats.max[3], 1.0f);
+    // Mono grain is the same in every color channel
+    OIIO_CHECK_EQUAL(R.getchannel(10, 20, 0, 0), R.getchannel(10, 20, 0, 2));
+
+    // Same seed gives the same grain, a different seed does not
+    ImageBuf R2 = ImageBufAlgo::film_grain(A, 0.05f, 2.0f, 1);
+    comp        = ImageBufAlgo::compare(R, R2, 0.0f, 0.0f);
+    OIIO_CHECK_EQUAL(comp.nfail, 0);
+    R2   = ImageBufAlgo::film_grain(A, 0.05f, 2.0f, 2);
+    comp = ImageBufAlgo::compare(R, R2, 0.0f, 0.0f);
+    OIIO_CHECK_GE(comp.nfail, 1);
+
+    // The grain is as strong along the edges of the ROI as in its interior
+    ImageBuf B = filled_image(grey, 512, 64);
+    R          = ImageBufAlgo::film_grain(B, 0.05f, 6.0f, 3, false);
+
+    auto variance = [&](ROI roi) {
+        auto s   = ImageBufAlgo::computePixelStats(R, roi);
+        double v = 0.0;
+        for (int c = 0; c < 3; ++c)
+            v += double(s.stddev[c]) * double(s.stddev[c]);
+        return v / 3.0;
+    };
+    double topvar      = variance(ROI(0, 512, 0, 1));
+    double bottomvar   = variance(ROI(0, 512, 63, 64));
+    double edgevar     = 0.5 * (topvar + bottomvar);
+    double interiorvar = variance(ROI(8, 504, 16, 48));
+    OIIO_CHECK_GT(edgevar / interiorvar, 0.85);
+    OIIO_CHECK_LT(edgevar / interiorvar, 1.15);
+}
+
+
+
+// Tests ImageBufAlgo::connected_components
+void
+test_connected_components()
+{
+    std::cout << "test connected_components\n";
+    // Two separated blobs, plus two pixels touching only diagonally
+    const float zero[1] = { 0.0f }, one[1] = { 1.0f };
+    ImageBuf A          = filled_image(zero, 10, 8);
+    ImageBufAlgo::fill(A, one, ROI(1, 4, 1, 3));
+    ImageBufAlgo::fill(A, one, ROI(6, 9, 2, 6));
+    ImageBufAlgo::fill(A, one, ROI(1, 2, 5, 6));
+    ImageBufAlgo::fill(A, one, ROI(2, 3, 6, 7));
+
+    ImageBuf L;
+    int n = 0;
+    OIIO_CHECK_ASSERT(ImageBufAlgo::connected_components(L, A, 0, 0.5f, 4, &n));
+    OIIO_CHECK_EQUAL(n, 4);
+    OIIO_CHECK_EQUAL(L.nchannels(), 1);
+    OIIO_CHECK_EQUAL(L.spec().channel_name(0), "label");
+    OIIO_CHECK_EQUAL(L.getchannel(0, 0, 0, 0), 0.0f);
+    // Labels are numbered in scanline order, and shared within a blob
+    OIIO_CHECK_EQUAL(L.getchannel(1, 1, 0, 0), 1.0f);
+    OIIO_CHECK_EQUAL(L.getchannel(3, 2, 0, 0), 1.0f);
+    OIIO_CHECK_EQUAL(L.getchannel(6, 2, 0, 0), 2.0f);
+    OIIO_CHECK_EQUAL(L.getchannel(8, 5, 0, 0), 2.0f);
+    OIIO_CHECK_EQUAL(L.getchannel(1, 5, 0, 0), 3.0f);
+    OIIO_CHECK_EQUAL(L.getchannel(2, 6, 0, 0), 4.0f);
+
+    // With 8-connectivity, the diagonal pair is one region
+    OIIO_CHECK_ASSERT(ImageBufAlgo::connected_components(L, A, 0, 0.5f, 8, &n));
+    OIIO_CHECK_EQUAL(n, 3);
+    OIIO_CHECK_EQUAL(L.getchannel(2, 6, 0, 0), 3.0f);
+
+    // A U shape whose arms only join at the bottom is still one region
+    ImageBuf U = filled_image(zero, 5, 5);
+    ImageBufAlgo::fill(U, one, ROI(0, 1, 0, 5));
+    ImageBufAlgo::fill(U, one, ROI(4, 5, 0, 5));
+    ImageBufAlgo::fill(U, one, ROI(0, 5, 4, 5));
+    L = ImageBufAlgo::connected_components(U, 0, 0.5f, 4);
+    OIIO_CHECK_EQUAL(L.getchannel(0, 0, 0, 0), 1.0f);
+    OIIO_CHECK_EQUAL(L.getchannel(4, 0, 0, 0), 1.0f);
+    OIIO_CHECK_EQUAL(L.getchannel(2, 4, 0, 0), 1.0f);
+}
+
+
+
+// Tests ImageBufAlgo::estimate_translation
+void
+test_estimate_translation()
+{
+    std::cout << "test estimate_translation\n";
+    // A few soft blobs of different sizes, so there is only one good match
+    ImageBuf A(ImageSpec(32, 32, 1, TypeDesc::FLOAT));
+    const float blobs[4][3] = {
+        { 8, 10, 3 }, { 20, 6, 2 }, { 14, 22, 4 }, { 25, 24, 2.5f }
+    };
+    for (ImageBuf::Iterator<float> a(A); !a.done(); ++a) {
+        float v = 0.0f;
+        for (auto& b : blobs) {
+            float ddx = a.x() - b[0], ddy = a.y() - b[1];
+            v += expf(-(ddx * ddx + ddy * ddy) / (2.0f * b[2] * b[2]));
+        }
+        a[0] = v;
+    }
+
+    // Shift by (3,-2) and recover the offset
+    ImageBuf B = ImageBufAlgo::circular_shift(A, 3, -2);
+    float dx = 0.0f, dy = 0.0f;
+    OIIO_CHECK_ASSERT(ImageBufAlgo::estimate_translation(A, B, dx, dy, 8));
+    OIIO_CHECK_EQUAL_THRESH(dx, 3.0f, 0.15f);
+    OIIO_CHECK_EQUAL_THRESH(dy, -2.0f, 0.15f);
+
+    // An image is aligned with itself
+    OIIO_CHECK_ASSERT(ImageBufAlgo::estimate_translation(A, A, dx, dy));
+    OIIO_CHECK_EQUAL(dx, 0.0f);
+    OIIO_CHECK_EQUAL(dy, 0.0f);
+
+    // A larger image and shift, found by the coarse-to-fine search
+    ImageBuf C(ImageSpec(256, 192, 1, TypeDesc::FLOAT));
+    ImageBuf D(C.spec());
+    auto field = [](float x, float y) {
+        float v = 0.0f;
+        for (int i = 0; i < 24; ++i) {
+            float ddx = x - float((i * 97) % 256);
+            float ddy = y - float((i * 61) % 192);
+            float r   = float(3 + i % 7);
+            v += expf(-(ddx * ddx + ddy * ddy) / (2.0f * r * r));
+        }
+        return v;
+    };
+    for (ImageBuf::Iterator<float> c(C); !c.done(); ++c)
+        c[0] = field(c.x(), c.y());
+    for (ImageBuf::Iterator<float> d(D); !d.done(); ++d)
+        d[0] = field(d.x() - 13.0f, d.y() + 9.0f);
+    OIIO_CHECK_ASSERT(ImageBufAlgo::estimate_translation(C, D, dx, dy, 16));
+    OIIO_CHECK_EQUAL_THRESH(dx, 13.0f, 0.15f);
+    OIIO_CHECK_EQUAL_THRESH(dy, -9.0f, 0.15f);
+}
+
+
+
+// Tests ImageBufAlgo::upscale
+void
+test_upscale()
+{
+    std::cout << "test upscale\n";
+    // Black on the left, white on the right
+    const float black[] = { 0.0f };
+    const float white[] = { 1.0f };
+    ImageBuf A          = filled_image(black, 16, 4);
+    ImageBufAlgo::fill(A, white, ROI(8, 16, 0, 4));
+
+    // Plain Lanczos rings on both sides of the edge
+    ImageBuf R = ImageBufAlgo::upscale(A, 2.0f, "lanczos3", false);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.roi(), ROI(0, 32, 0, 8, 0, 1, 0, 1));
+    OIIO_CHECK_EQUAL(R.roi_full(), R.roi());
+    auto stats = ImageBufAlgo::computePixelStats(R);
+    OIIO_CHECK_LT(stats.min[0], -0.05f);
+    OIIO_CHECK_GT(stats.max[0], 1.05f);
+
+    // Deringing removes the overshoot but keeps the edge
+    R     = ImageBufAlgo::upscale(A, 2.0f, "lanczos3", true);
+    stats = ImageBufAlgo::computePixelStats(R);
+    OIIO_CHECK_GE(stats.min[0], 0.0f);
+    OIIO_CHECK_LE(stats.max[0], 1.0f);
+    OIIO_CHECK_EQUAL(R.getchannel(2, 2, 0, 0), 0.0f);
+    OIIO_CHECK_EQUAL(R.getchannel(29, 2, 0, 0), 1.0f);
+    OIIO_CHECK_GT(R.getchannel(16, 2, 0, 0), 0.5f);
+    OIIO_CHECK_LT(R.getchannel(15, 2, 0, 0), 0.5f);
+
+    R = ImageBufAlgo::upscale(A, 0.0f);
+    OIIO_CHECK_ASSERT(R.has_error());
+}
+
+
+
+// Tests ImageBufAlgo::seam_carve
+void
+test_seam_carve()
+{
+    std::cout << "test seam_carve\n";
+    // Grey image with a bright vertical bar in columns 5 and 6
+    const float grey[]  = { 0.5f };
+    const float white[] = { 1.0f };
+    ImageBuf A          = filled_image(grey, 12, 6);
+    ImageBufAlgo::fill(A, white, ROI(5, 7, 0, 6));
+
+    // Removing columns takes them from the flat areas, keeping the bar
+    ImageBuf R = ImageBufAlgo::seam_carve(A, 8, 6);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.spec().width, 8);
+    OIIO_CHECK_EQUAL(R.spec().height, 6);
+    OIIO_CHECK_EQUAL(R.spec().full_width, 8);
+    auto stats = ImageBufAlgo::computePixelStats(R);
+    OIIO_CHECK_EQUAL(stats.max[0], 1.0f);
+    for (int y = 0; y < 6; ++y) {
+        int nbright = 0;
+        for (int x = 0; x < 8; ++x)
+            nbright += R.getchannel(x, y, 0, 0) == 1.0f;
+        OIIO_CHECK_EQUAL(nbright, 2);
+    }
+
+    // Removing rows as well leaves the full-height bar intact
+    R = ImageBufAlgo::seam_carve(A, 8, 4);
+    OIIO_CHECK_EQUAL(R.spec().height, 4);
+    for (int y = 0; y < 4; ++y) {
+        int nbright = 0;
+        for (int x = 0; x < 8; ++x)
+            nbright += R.getchannel(x, y, 0, 0) == 1.0f;
+        OIIO_CHECK_EQUAL(nbright, 2);
+    }
+
+    // Can't grow the image
+    R = ImageBufAlgo::seam_carve(A, 13, 6);
+    OIIO_CHECK_ASSERT(R.has_error());
+}
+
+
+
+// Tests ImageBufAlgo::sobel and gradient_magnitude
+void
+test_sobel()
+{
+    std::cout << "test sobel\n";
+    // Gradient of a constant image is zero
+    const float grey[] = { 0.5f };
+    ImageBuf A         = filled_image(grey, 8, 8);
+
+    auto stats = ImageBufAlgo::computePixelStats(
+        ImageBufAlgo::gradient_magnitude(A));
+    OIIO_CHECK_EQUAL(stats.max[0], 0.0f);
+
+    // A vertical edge: grey on the left, white on the right
+    const float white[] = { 1.0f };
+    ImageBufAlgo::fill(A, white, ROI(4, 8, 0, 8));
+    ImageBuf Gx, Gy;
+    OIIO_CHECK_ASSERT(ImageBufAlgo::sobel(Gx, Gy, A));
+    OIIO_CHECK_EQUAL(Gx.spec().format, TypeFloat);
+    OIIO_CHECK_EQUAL(Gx.getchannel(3, 4, 0, 0), 2.0f);
+    OIIO_CHECK_EQUAL(Gx.getchannel(4, 4, 0, 0), 2.0f);
+    OIIO_CHECK_EQUAL(Gx.getchannel(1, 4, 0, 0), 0.0f);
+    stats = ImageBufAlgo::computePixelStats(Gy);
+    OIIO_CHECK_EQUAL(stats.min[0], 0.0f);
+    OIIO_CHECK_EQUAL(stats.max[0], 0.0f);
+
+    ImageBuf M = ImageBufAlgo::gradient_magnitude(A);
+    OIIO_CHECK_EQUAL_THRESH(M.getchannel(4, 4, 0, 0), 2.0f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(M.getchannel(6, 4, 0, 0), 0.0f, 1e-6f);
+
+    // An 8-bit source still gets a float magnitude, which may exceed 1
+    ImageBuf B(ImageSpec(8, 8, 1, TypeDesc::UINT8));
+    ImageBufAlgo::fill(B, white, ROI(4, 8, 0, 8));
+    M = ImageBufAlgo::gradient_magnitude(B);
+    OIIO_CHECK_EQUAL(M.spec().format, TypeFloat);
+    OIIO_CHECK_EQUAL_THRESH(M.getchannel(4, 4, 0, 0), 4.0f, 1e-6f);
+}
+
+
+
+// Tests ImageBufAlgo::bilateral_filter
+void
+test_bilateral_filter()
+{
+    std::cout << "test bilateral_filter\n";
+    // A sharp vertical edge, dark on the left and bright on the right, with
+    // a small amount of deterministic "noise" added.
+    ImageBuf A(ImageSpec(16, 8, 1, TypeDesc::FLOAT));
+    for (ImageBuf::Iterator<float> a(A); !a.done(); ++a) {
+        float noise = 0.01f * float((a.x() * 7 + a.y() * 13) % 5 - 2);
+        a[0]        = (a.x() < 8 ? 0.2f : 0.8f) + noise;
+    }
+
+    ImageBuf R = ImageBufAlgo::bilateral_filter(A, 1.5f, 0.1f);
+    OIIO_CHECK_ASSERT(!R.has_error());
+
+    // The edge survives
+    OIIO_CHECK_GE(R.getchannel(8, 4, 0, 0) - R.getchannel(7, 4, 0, 0), 0.55f);
+
+    // The flat regions get smoother
+    ROI flat(0, 6, 0, 8);
+    auto before = ImageBufAlgo::computePixelStats(A, flat);
+    auto after  = ImageBufAlgo::computePixelStats(R, flat);
+    OIIO_CHECK_LE(after.stddev[0], 0.5f * before.stddev[0]);
+    OIIO_CHECK_EQUAL_THRESH(after.avg[0], before.avg[0], 0.01f);
+
+    // Non-positive sigmas are an error
+    R = ImageBufAlgo::bilateral_filter(A, 0.0f, 0.1f);
+    OIIO_CHECK_ASSERT(R.has_error());
+}
+
+
+
+// Tests ImageBufAlgo::joint_bilateral_upsample
+void
+test_joint_bilateral_upsample()
+{
+    std::cout << "test joint_bilateral_upsample\n";
+    // A 2x2 mask, off on the left and on on the right
+    ImageBuf mask(ImageSpec(2, 2, 1, TypeDesc::FLOAT));
+    for (ImageBuf::Iterator<float> m(mask); !m.done(); ++m)
+        m[0] = float(m.x());
+    // A 4x4 guide with a sharp vertical edge down the middle
+    ImageBuf guide(ImageSpec(4, 4, 1, TypeDesc::FLOAT));
+    for (ImageBuf::Iterator<float> g(guide); !g.done(); ++g)
+        g[0] = g.x() < 2 ? 0.1f : 0.9f;
+
+    ImageBuf R = ImageBufAlgo::joint_bilateral_upsample(mask, guide, 1.0f,
+                                                        0.1f);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.spec().width, 4);
+    OIIO_CHECK_EQUAL(R.spec().height, 4);
+    OIIO_CHECK_EQUAL(R.nchannels(), 1);
+    // The result follows the guide's edge: it changes sharply between
+    // columns 1 and 2 rather than ramping across the image.
+    for (int y = 0; y < 4; ++y) {
+        OIIO_CHECK_LE(R.getchannel(1, y, 0, 0), 0.01f);
+        OIIO_CHECK_GE(R.getchannel(2, y, 0, 0), 0.99f);
+    }
+
+    // With a featureless guide, it's just a smooth interpolation
+    ImageBuf flat = filled_image({ 0.5f });
+    R = ImageBufAlgo::joint_bilateral_upsample(mask, flat, 1.0f, 0.1f);
+    OIIO_CHECK_GE(R.getchannel(1, 0, 0, 0), 0.1f);
+    OIIO_CHECK_LE(R.getchannel(2, 0, 0, 0), 0.9f);
+
+    // Non-positive sigmas are an error
+    R = ImageBufAlgo::joint_bilateral_upsample(mask, guide, 1.0f, 0.0f);
+    OIIO_CHECK_ASSERT(R.has_error());
+}
+
+
+
+// Tests ImageBufAlgo::clarity
+void
+test_clarity()
+{
+    std::cout << "test clarity\n";
+    // Low-contrast stripes on the left, a flat bright region on the right
+    ImageBuf A(ImageSpec(16, 8, 1, TypeDesc::FLOAT));
+    for (ImageBuf::Iterator<float> a(A); !a.done(); ++a) {
+        if (a.x() < 8)
+            a[0] = (a.x() / 2) % 2 ? 0.55f : 0.45f;
+        else
+            a[0] = 0.8f;
+    }
+
+    // Zero amount is a no-op
+    ImageBuf R = ImageBufAlgo::clarity(A, 0.0f, 2.0f);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    auto comp = ImageBufAlgo::compare(A, R, 1e-6f, 1e-6f);
+    OIIO_CHECK_EQUAL(comp.maxerror, 0.0f);
+
+    // A positive amount boosts the local contrast of the stripes...
+    R = ImageBufAlgo::clarity(A, 1.0f, 2.0f);
+    ROI textured(1, 7, 0, 8);
+    auto before = ImageBufAlgo::computePixelStats(A, textured);
+    auto after  = ImageBufAlgo::computePixelStats(R, textured);
+    OIIO_CHECK_GE(after.stddev[0], 1.3f * before.stddev[0]);
+    // ...but leaves the flat region alone, even next to the strong edge
+    auto flat = ImageBufAlgo::computePixelStats(R, ROI(8, 16, 0, 8));
+    OIIO_CHECK_LE(flat.max[0], 0.81f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(12, 4, 0, 0), 0.8f, 1e-3f);
+
+    // The same image scaled up to HDR values needs a proportionally larger
+    // range sigma to get the same result
+    ImageBuf H = ImageBufAlgo::mul(A, 10.0f);
+    R          = ImageBufAlgo::clarity(H, 1.0f, 2.0f, 1.0f);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    before = ImageBufAlgo::computePixelStats(H, textured);
+    after  = ImageBufAlgo::computePixelStats(R, textured);
+    OIIO_CHECK_GE(after.stddev[0], 1.3f * before.stddev[0]);
+
+    // A non-positive range sigma is an error
+    R = ImageBufAlgo::clarity(A, 1.0f, 2.0f, 0.0f);
+    OIIO_CHECK_ASSERT(R.has_error());
+}
+
+
+
+// Tests ImageBufAlgo::poisson_clone
+void
+test_poisson_clone()
+{
+    std::cout << "test poisson_clone\n";
+    const float bgcolor[3] = { 0.2f, 0.3f, 0.4f };
+    ImageBuf Bg            = filled_image(bgcolor, 24, 24);
+    // Bright source with an even brighter spot in the middle
+    const float srccolor[3] = { 0.8f, 0.8f, 0.8f };
+    const float spot[3]     = { 1.0f, 1.0f, 1.0f };
+    ImageBuf Src            = filled_image(srccolor, 12, 12);
+    ImageBufAlgo::fill(Src, spot, ROI(5, 7, 5, 7));
+    const float zero[1] = { 0.0f }, one[1] = { 1.0f };
+    ImageBuf Mask       = filled_image(zero, 12, 12);
+    ImageBufAlgo::fill(Mask, one, ROI(1, 11, 1, 11));
+
+    ImageBuf R = ImageBufAlgo::poisson_clone(Bg, Src, Mask, 6, 6);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.roi(), Bg.roi());
+
+    // The cloned region takes on the background color, so there is no
+    // seam at its border...
+    for (int c = 0; c < 3; ++c) {
+        OIIO_CHECK_EQUAL_THRESH(R.getchannel(7, 12, 0, c), bgcolor[c], 1e-3f);
+        OIIO_CHECK_EQUAL_THRESH(R.getchannel(16, 12, 0, c), bgcolor[c],
+                                1e-3f);
+        OIIO_CHECK_EQUAL_THRESH(R.getchannel(12, 7, 0, c), bgcolor[c], 1e-3f);
+        OIIO_CHECK_EQUAL_THRESH(R.getchannel(12, 16, 0, c), bgcolor[c],
+                                1e-3f);
+        // ...while the spot keeps its contrast relative to its surroundings
+        OIIO_CHECK_EQUAL_THRESH(R.getchannel(11, 11, 0, c), bgcolor[c] + 0.2f,
+                                1e-3f);
+        // Pixels outside the clone region are untouched
+        OIIO_CHECK_EQUAL(R.getchannel(2, 2, 0, c), bgcolor[c]);
+    }
+
+    // An empty mask leaves the background as it was
+    R         = ImageBufAlgo::poisson_clone(Bg, Src, filled_image(zero, 12, 12));
+    auto comp = ImageBufAlgo::compare(R, Bg, 0.0f, 0.0f);
+    OIIO_CHECK_EQUAL(comp.nfail, 0);
+}
+
+
+
+// Tests ImageBufAlgo::exposure_fusion
+void
+test_exposure_fusion()
+{
+    std::cout << "test exposure_fusion\n";
+    // A horizontal ramp, under exposed so that its dark half is crushed to
+    // black, and over exposed so that its bright half is blown out.
+    ImageSpec spec(64, 16, 1, TypeFloat);
+    ImageBuf Under(spec), Over(spec);
+    for (ImageBuf::Iterator<float> u(Under), o(Over); !u.done(); ++u, ++o) {
+        float s = (u.x() + 0.5f) / spec.width;
+        u[0]    = clamp(2.0f * s - 1.0f, 0.0f, 1.0f);
+        o[0]    = clamp(2.0f * s, 0.0f, 1.0f);
+    }
+    auto well_exposed = [](const ImageBuf& img) {
+        int n = 0;
+        for (ImageBuf::ConstIterator<float> p(img); !p.done(); ++p)
+            if (p[0] > 0.05f && p[0] < 0.95f)
+                ++n;
+        return n;
+    };
+
+    // A linear ramp has no contrast, so weight by exposure alone. The
+    // fused result should use more of the tonal range than either input.
+    std::vector<ImageBuf> brackets { Under, Over };
+    ImageBuf R = ImageBufAlgo::exposure_fusion(brackets, 0.0f, 1.0f, 1.0f);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.roi(), Under.roi());
+    OIIO_CHECK_GT(well_exposed(R), well_exposed(Under));
+    OIIO_CHECK_GT(well_exposed(R), well_exposed(Over));
+
+    // Fusing copies of one image gives back that image
+    std::vector<ImageBuf> same { Under, Under };
+    R         = ImageBufAlgo::exposure_fusion(same);
+    auto comp = ImageBufAlgo::compare(R, Under, 1e-4f, 1e-4f);
+    OIIO_CHECK_EQUAL(comp.nfail, 0);
+
+    // Errors: no images, or images of different sizes
+    R = ImageBufAlgo::exposure_fusion({});
+    OIIO_CHECK_ASSERT(R.has_error());
+    ImageBuf Small(ImageSpec(8, 8, 1, TypeFloat));
+    std::vector<ImageBuf> mismatched { Under, Small };
+    R = ImageBufAlgo::exposure_fusion(mismatched);
+    OIIO_CHECK_ASSERT(R.has_error());
+}
+
+
+
 // Test ImageBuf::over
 void
 test_over(TypeDesc dtype = TypeFloat)
@@ -637,6 +1343,105 @@ test_zover()
 
 
 
+// Tests ImageBufAlgo::chroma_key
+void
+test_chroma_key()
+{
+    std::cout << "test chroma_key\n";
+    // A red square on a green field
+    ImageBuf A = filled_image({ 0.0f, 1.0f, 0.0f }, 8, 8);
+    ImageBufAlgo::fill(A, { 1.0f, 0.0f, 0.0f }, ROI(2, 6, 2, 6));
+    // One foreground pixel with some green spill
+    const float spilled[] = { 0.3f, 0.7f, 0.2f };
+    A.setpixel(0, 7, spilled);
+
+    const float green[] = { 0.0f, 1.0f, 0.0f };
+    ImageBuf R          = ImageBufAlgo::chroma_key(A, green, 0.1f, 0.1f);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.nchannels(), 4);
+    OIIO_CHECK_EQUAL(R.spec().alpha_channel, 3);
+    // The green field becomes transparent, the red square stays opaque
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(0, 0, 0, 3), 0.0f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(0, 0, 0, 1), 0.0f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(3, 3, 0, 3), 1.0f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(3, 3, 0, 0), 1.0f, 1e-6f);
+    // The spill is limited to the larger of red and blue
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(0, 7, 0, 3), 1.0f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(0, 7, 0, 1), 0.3f, 1e-6f);
+
+    // Without despill, the foreground color is untouched
+    R = ImageBufAlgo::chroma_key(A, green, 0.1f, 0.1f, false);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(0, 7, 0, 1), 0.7f, 1e-6f);
+
+    // The matte lines up with a source whose data window is offset from
+    // the origin and smaller than its display window
+    ImageSpec ospec(8, 8, 3, TypeFloat);
+    ospec.x           = 10;
+    ospec.y           = 20;
+    ospec.full_x      = 8;
+    ospec.full_y      = 16;
+    ospec.full_width  = 16;
+    ospec.full_height = 16;
+    ImageBuf B(ospec);
+    ImageBufAlgo::fill(B, green);
+    ImageBufAlgo::fill(B, { 1.0f, 0.0f, 0.0f }, ROI(12, 16, 22, 26));
+    R = ImageBufAlgo::chroma_key(B, green, 0.1f, 0.1f);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.roi(), ROI(10, 18, 20, 28, 0, 1, 0, 4));
+    OIIO_CHECK_EQUAL(R.roi_full(), ROI(8, 24, 16, 32, 0, 1, 0, 4));
+    OIIO_CHECK_EQUAL(R.spec().alpha_channel, 3);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(10, 20, 0, 3), 0.0f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(13, 23, 0, 3), 1.0f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(13, 23, 0, 0), 1.0f, 1e-6f);
+}
+
+
+
+// Tests ImageBufAlgo::flatten and its options
+void
+test_flatten()
+{
+    std::cout << "test flatten\n";
+    // A one-pixel deep image with three samples, given back to front
+    ImageSpec spec(1, 1, 3, TypeDesc::FLOAT);
+    spec.channelnames  = { "R", "A", "Z" };
+    spec.alpha_channel = 1;
+    spec.z_channel     = 2;
+    spec.deep          = true;
+    ImageBuf deep(spec);
+    const float samples[3][3] = { { 0.2f, 1.0f, 3.0f },
+                                  { 0.6f, 0.6f, 2.0f },
+                                  { 0.6f, 0.6f, 1.0f } };
+    deep.set_deep_samples(0, 0, 0, 3);
+    for (int s = 0; s < 3; ++s)
+        for (int c = 0; c < 3; ++c)
+            deep.set_deep_value(0, 0, 0, c, s, samples[s][c]);
+
+    // Unsorted: the opaque back sample is composited first
+    ImageBuf flat = ImageBufAlgo::flatten(deep);
+    OIIO_CHECK_EQUAL_THRESH(flat.getchannel(0, 0, 0, 0), 0.2f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(flat.getchannel(0, 0, 0, 1), 1.0f, 1e-6f);
+
+    // Sorted by Z, front to back: 0.6 + 0.4*0.6 + 0.16*0.2
+    flat = ImageBufAlgo::flatten(deep, { { "sort", 1 } });
+    OIIO_CHECK_EQUAL_THRESH(flat.getchannel(0, 0, 0, 0), 0.872f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(flat.getchannel(0, 0, 0, 1), 1.0f, 1e-6f);
+
+    // An alpha threshold of 1.0 is the same as the default
+    ImageBuf flat1 = ImageBufAlgo::flatten(deep, { { "sort", 1 },
+                                                   { "alpha_threshold",
+                                                     1.0f } });
+    OIIO_CHECK_EQUAL(ImageBufAlgo::compare(flat, flat1, 0.0f, 0.0f).nfail, 0);
+
+    // A low threshold stops after the first sample
+    flat = ImageBufAlgo::flatten(deep,
+                                 { { "sort", 1 }, { "alpha_threshold", 0.5f } });
+    OIIO_CHECK_EQUAL_THRESH(flat.getchannel(0, 0, 0, 0), 0.6f, 1e-6f);
+    OIIO_CHECK_EQUAL_THRESH(flat.getchannel(0, 0, 0, 1), 0.6f, 1e-6f);
+}
+
+
+
 // Tests ImageBufAlgo::compare
 void
 test_compare()
@@ -709,6 +1514,38 @@ test_compare()
 
 
 
+// Tests ImageBufAlgo::content_equals
+void
+test_content_equals()
+{
+    std::cout << "test content_equals\n";
+    ImageSpec spec(4, 4, 3, TypeDesc::FLOAT);
+    ImageBuf A(spec);
+    const float grey[3] = { 0.5f, 0.5f, 0.5f };
+    ImageBufAlgo::fill(A, grey);
+    ImageBuf B = A.copy(TypeUInt16);
+    B.specmod().attribute("Artist", "someone");
+
+    // Same pixels, differing metadata and pixel data format
+    OIIO_CHECK_ASSERT(ImageBufAlgo::content_equals(A, B, false, 1e-4f));
+    OIIO_CHECK_ASSERT(!ImageBufAlgo::content_equals(A, B, true, 1e-4f));
+    A.specmod().attribute("Artist", "someone");
+    OIIO_CHECK_ASSERT(ImageBufAlgo::content_equals(A, B, true, 1e-4f));
+
+    // Pixel differences within and beyond the tolerance
+    ImageBuf C           = A.copy();
+    const float other[3] = { 0.5f, 0.52f, 0.5f };
+    C.setpixel(1, 1, other);
+    OIIO_CHECK_ASSERT(!ImageBufAlgo::content_equals(A, C));
+    OIIO_CHECK_ASSERT(ImageBufAlgo::content_equals(A, C, true, 0.05f));
+
+    // Differing data windows never match
+    ImageBuf D = ImageBufAlgo::cut(A, ROI(0, 2, 0, 2));
+    OIIO_CHECK_ASSERT(!ImageBufAlgo::content_equals(A, D));
+}
+
+
+
 // Tests ImageBufAlgo::isConstantColor
 void
 test_isConstantColor()
@@ -829,6 +1666,54 @@ test_computePixelStats()
 
 
 
+// Tests the ImageInput (streaming) variety of computePixelStats()
+void
+test_computePixelStats_streaming()
+{
+    std::cout << "test computePixelStats streaming\n";
+    ImageSpec spec(96, 160, 3, TypeDesc::FLOAT);
+    ImageBuf img(spec);
+    ImageBufAlgo::noise(img, "uniform", 0.0f, 1.0f);
+
+    // Write the noisy image to an in-memory file, both tiled and as
+    // scanlines, since the two are read differently
+    for (int tilesize : { 32, 0 }) {
+        std::vector<unsigned char> file;
+        Filesystem::IOVecOutput writer(file);
+        img.set_write_tiles(tilesize, tilesize);
+        img.set_write_ioproxy(&writer);
+        OIIO_CHECK_ASSERT(img.write("stats.tif"));
+
+        Filesystem::IOMemReader reader(file);
+        auto in = ImageInput::open("stats.tif", nullptr, &reader);
+        OIIO_CHECK_ASSERT(in);
+        if (!in)
+            continue;
+        OIIO_CHECK_EQUAL(in->spec().tile_width, tilesize);
+        for (ROI roi : { ROI(), ROI(10, 50, 20, 130, 0, 1, 1, 3) }) {
+            auto stats  = ImageBufAlgo::computePixelStats(*in, 0, 0, roi);
+            auto ref    = ImageBufAlgo::computePixelStats(img, roi);
+            int chbegin = roi.defined() ? roi.chbegin : 0;
+            int chend   = roi.defined() ? roi.chend : spec.nchannels;
+            OIIO_CHECK_ASSERT(!in->has_error());
+            OIIO_CHECK_EQUAL(stats.min.size(), ref.min.size());
+            if (stats.min.size() != ref.min.size())
+                continue;
+            for (int c = chbegin; c < chend; ++c) {
+                OIIO_CHECK_EQUAL(stats.min[c], ref.min[c]);
+                OIIO_CHECK_EQUAL(stats.max[c], ref.max[c]);
+                OIIO_CHECK_EQUAL_THRESH(stats.avg[c], ref.avg[c], 1e-6f);
+                OIIO_CHECK_EQUAL_THRESH(stats.stddev[c], ref.stddev[c],
+                                        1e-6f);
+                OIIO_CHECK_EQUAL(stats.finitecount[c], ref.finitecount[c]);
+            }
+        }
+    }
+    img.set_write_ioproxy(nullptr);
+}
+
+
+
 // Tests histogram computation.
 void
 histogram_computation_test()
@@ -878,6 +1763,103 @@ histogram_computation_test()
 
 
 
+// Tests that single-pixel images and regions work in the analysis and
+// resizing functions
+void
+test_tiny_images()
+{
+    std::cout << "test tiny images\n";
+    // Resize 2x2 down to 1x1 with a box filter gives the average
+    ImageBuf A(ImageSpec(2, 2, 1, TypeDesc::FLOAT));
+    for (ImageBuf::Iterator<float> a(A); !a.done(); ++a)
+        a[0] = float(a.y() * 2 + a.x());  // 0, 1, 2, 3
+    ROI tiny(0, 1, 0, 1, 0, 1, 0, 1);
+    ImageBuf R = ImageBufAlgo::resize(A, { { "filtername", "box" } }, tiny);
+    OIIO_CHECK_ASSERT(!R.has_error());
+    OIIO_CHECK_EQUAL(R.spec().width, 1);
+    OIIO_CHECK_EQUAL(R.spec().height, 1);
+    OIIO_CHECK_EQUAL_THRESH(R.getchannel(0, 0, 0, 0), 1.5f, 1e-5f);
+
+    // Stats of a 1x1 image
+    ImageBuf one = filled_image({ 0.25f, 0.75f }, 1, 1);
+    auto stats   = ImageBufAlgo::computePixelStats(one);
+    OIIO_CHECK_EQUAL(stats.min[0], 0.25f);
+    OIIO_CHECK_EQUAL(stats.max[1], 0.75f);
+    OIIO_CHECK_EQUAL(stats.avg[0], 0.25f);
+    OIIO_CHECK_EQUAL(stats.stddev[1], 0.0f);
+}
+
+
+
+// Tests ImageBufAlgo::percentiles
+void
+test_percentiles()
+{
+    std::cout << "test percentiles\n";
+    // Horizontal ramp in channel 0 with values 0..100, constant channel 1
+    ImageBuf A(ImageSpec(101, 2, 2, TypeDesc::FLOAT));
+    for (ImageBuf::Iterator<float> a(A); !a.done(); ++a) {
+        a[0] = float(a.x());
+        a[1] = 3.0f;
+    }
+    const float pcts[] = { 0.0f, 25.0f, 50.0f, 100.0f };
+    auto p             = ImageBufAlgo::percentiles(A, pcts);
+    OIIO_CHECK_EQUAL(p.size(), 2);
+    OIIO_CHECK_EQUAL(p[0].size(), 4);
+    OIIO_CHECK_EQUAL(p[0][0], 0.0f);
+    OIIO_CHECK_EQUAL_THRESH(p[0][1], 25.0f, 1e-4f);
+    OIIO_CHECK_EQUAL_THRESH(p[0][2], 50.0f, 1e-4f);
+    OIIO_CHECK_EQUAL(p[0][3], 100.0f);
+    OIIO_CHECK_EQUAL(p[1][0], 3.0f);
+    OIIO_CHECK_EQUAL(p[1][3], 3.0f);
+
+    // NaNs are ignored
+    const float nanpixel[2] = { std::numeric_limits<float>::quiet_NaN(), 3.0f };
+    A.setpixel(50, 0, nanpixel);
+    p = ImageBufAlgo::percentiles(A, pcts, ROI(0, 101, 0, 1, 0, 1, 0, 1));
+    OIIO_CHECK_EQUAL(p.size(), 1);
+    OIIO_CHECK_EQUAL(p[0][0], 0.0f);
+    OIIO_CHECK_EQUAL(p[0][3], 100.0f);
+
+    // Out-of-range percentiles are an error
+    const float bad[] = { 101.0f };
+    p = ImageBufAlgo::percentiles(A, bad);
+    OIIO_CHECK_ASSERT(p.empty() && A.has_error());
+    A.geterror();
+}
+
+
+
+// Tests ImageBufAlgo::palette
+void
+test_palette()
+{
+    std::cout << "test palette\n";
+    // Two blocks of distinct colors, the red one larger than the blue one
+    const float red[]  = { 0.9f, 0.1f, 0.1f };
+    const float blue[] = { 0.1f, 0.2f, 0.8f };
+    ImageBuf A         = filled_image(red, 8, 4);
+    ImageBufAlgo::fill(A, blue, ROI(5, 8, 0, 4));
+
+    auto p = ImageBufAlgo::palette(A, 2);
+    OIIO_CHECK_EQUAL(p.size(), 2);
+    for (int c = 0; c < 3; ++c) {
+        OIIO_CHECK_EQUAL_THRESH(p[0][c], red[c], 1e-5f);
+        OIIO_CHECK_EQUAL_THRESH(p[1][c], blue[c], 1e-5f);
+    }
+
+    // Asking for more colors than there are only finds the distinct ones
+    p = ImageBufAlgo::palette(A, 4);
+    OIIO_CHECK_EQUAL(p.size(), 2);
+
+    // k must be positive
+    p = ImageBufAlgo::palette(A, 0);
+    OIIO_CHECK_ASSERT(p.empty() && A.has_error());
+    A.geterror();
+}
+
+
+
 // Test ability to do a maketx directly from an ImageBuf
 void
 test_maketx_from_imagebuf()
@@ -1205,6 +2187,10 @@ main(int argc, char** argv)
     test_copy();
     test_crop();
     test_paste();
+    test_flip_axes();
+    test_upscale();
+    test_seam_carve();
+    test_channels_by_name();
     test_channel_append();
     test_add();
     test_sub();
@@ -1212,15 +2198,35 @@ main(int argc, char** argv)
     test_mad();
     test_min();
     test_max();
+    test_curve_remap();
+    test_tonemap();
+    test_white_balance();
+    test_vignette();
+    test_film_grain();
+    test_sobel();
+    test_bilateral_filter();
+    test_joint_bilateral_upsample();
+    test_clarity();
+    test_poisson_clone();
+    test_exposure_fusion();
     test_over(TypeFloat);
     test_over(TypeHalf);
     test_zover();
+    test_chroma_key();
     test_compare();
+    test_content_equals();
+    test_flatten();
     test_isConstantColor();
     test_isConstantChannel();
     test_isMonochrome();
+    test_connected_components();
+    test_estimate_translation();
     test_computePixelStats();
+    test_computePixelStats_streaming();
     histogram_computation_test();
+    test_tiny_images();
+    test_percentiles();
+    test_palette();
     test_maketx_from_imagebuf();
     test_IBAprep();
     test_validate_st_warp_checks();
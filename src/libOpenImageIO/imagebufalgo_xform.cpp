@@ -6,6 +6,7 @@
 /// ImageBufAlgo functions for filtered transformations
 
 
+#include <algorithm>
 #include <cmath>
 #include <memory>
 
@@ -1253,6 +1254,108 @@ ImageBufAlgo::fit(ImageBuf& dst, const ImageBuf& src, Filter2D* filter,
 
 
 
+// Clamp each pixel of dst to the range of the 2x2 block of src pixels
+// nearest to where it samples src, removing filter overshoot.
+template<typename DSTTYPE, typename SRCTYPE>
+static bool
+dering_(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads)
+{
+    ImageBufAlgo::parallel_image(roi, nthreads, [&](ROI roi) {
+        const ImageSpec& srcspec(src.spec());
+        const ImageSpec& dstspec(dst.spec());
+        float xscale = float(srcspec.full_width) / float(dstspec.full_width);
+        float yscale = float(srcspec.full_height) / float(dstspec.full_height);
+        ImageBuf::ConstIterator<SRCTYPE> s(src, ImageBuf::WrapClamp);
+        for (ImageBuf::Iterator<DSTTYPE> d(dst, roi); !d.done(); ++d) {
+            // Upper left of the 2x2 src pixels around the dst pixel center
+            float sx = srcspec.full_x
+                       + (d.x() - dstspec.full_x + 0.5f) * xscale - 0.5f;
+            float sy = srcspec.full_y
+                       + (d.y() - dstspec.full_y + 0.5f) * yscale - 0.5f;
+            int x0   = ifloor(sx);
+            int y0   = ifloor(sy);
+            for (int c = roi.chbegin; c < roi.chend; ++c) {
+                float lo = std::numeric_limits<float>::max();
+                float hi = -std::numeric_limits<float>::max();
+                for (int j = 0; j < 2; ++j) {
+                    for (int i = 0; i < 2; ++i) {
+                        s.pos(x0 + i, y0 + j, d.z());
+                        lo = std::min(lo, float(s[c]));
+                        hi = std::max(hi, float(s[c]));
+                    }
+                }
+                d[c] = OIIO::clamp(float(d[c]), lo, hi);
+            }
+        }
+    });
+    return true;
+}
+
+
+
+bool
+ImageBufAlgo::upscale(ImageBuf& dst, const ImageBuf& src, float scale,
+                      string_view filtername, bool deringing, ROI roi,
+                      int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::upscale");
+    if (!(scale > 0.0f)) {
+        dst.errorfmt("upscale: scale must be positive (got {})", scale);
+        return false;
+    }
+    if (!src.initialized()) {
+        dst.errorfmt("Uninitialized input image");
+        return false;
+    }
+    if (!dst.initialized()) {
+        // Scale both the data and display windows of src
+        auto scaled = [scale](ROI r) {
+            ROI s    = r;
+            s.xbegin = int(lrintf(r.xbegin * scale));
+            s.ybegin = int(lrintf(r.ybegin * scale));
+            s.xend   = std::max(int(lrintf(r.xend * scale)), s.xbegin + 1);
+            s.yend   = std::max(int(lrintf(r.yend * scale)), s.ybegin + 1);
+            return s;
+        };
+        ImageSpec spec = src.spec();
+        set_roi(spec, scaled(src.roi()));
+        set_roi_full(spec, scaled(src.roi_full()));
+        spec.tile_width  = 0;
+        spec.tile_height = 0;
+        spec.tile_depth  = 0;
+        dst.reset(spec);
+    }
+    if (!IBAprep(roi, &dst, &src,
+                 IBAprep_NO_SUPPORT_VOLUME | IBAprep_NO_COPY_ROI_FULL))
+        return false;
+    roi.chend = std::min(roi.chend, src.nchannels());
+    if (!resize(dst, src, { { filtername_us, filtername } }, roi, nthreads))
+        return false;
+    if (!deringing)
+        return true;
+
+    bool ok;
+    OIIO_DISPATCH_COMMON_TYPES2(ok, "upscale", dering_, dst.spec().format,
+                                src.spec().format, dst, src, roi, nthreads);
+    return ok;
+}
+
+
+
+ImageBuf
+ImageBufAlgo::upscale(const ImageBuf& src, float scale, string_view filtername,
+                      bool deringing, ROI roi, int nthreads)
+{
+    ImageBuf result;
+    bool ok = upscale(result, src, scale, filtername, deringing, roi,
+                      nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::upscale() error");
+    return result;
+}
+
+
+
 template<typename DSTTYPE, typename SRCTYPE>
 static bool
 resample_(ImageBuf& dst, const ImageBuf& src, bool interpolate, ROI roi,
@@ -1383,6 +1486,177 @@ ImageBufAlgo::resample(const ImageBuf& src, bool interpolate, ROI roi,
 
 
 
+// Remove `nseams` vertical seams from the w x h x nc float image in
+// `pixels` (which is compacted in place), updating w.
+static void
+carve_columns(std::vector<float>& pixels, int& w, int h, int nc, int nseams,
+              int nthreads)
+{
+    std::vector<float> energy(size_t(w) * h);
+    std::vector<float> cost(size_t(w) * h);
+    std::vector<int> seam(h);
+    for (; nseams > 0; --nseams) {
+        // Energy: the sum over channels of the Sobel gradient magnitude,
+        // clamping at the image edges.
+        auto P = [&](int x, int y, int c) {
+            x = clamp(x, 0, w - 1);
+            y = clamp(y, 0, h - 1);
+            return pixels[(size_t(y) * w + x) * nc + c];
+        };
+        ImageBufAlgo::parallel_image(ROI(0, w, 0, h), nthreads, [&](ROI r) {
+            for (int y = r.ybegin; y < r.yend; ++y) {
+                for (int x = r.xbegin; x < r.xend; ++x) {
+                    float e = 0.0f;
+                    for (int c = 0; c < nc; ++c) {
+                        float gx = (P(x + 1, y - 1, c) + 2.0f * P(x + 1, y, c)
+                                    + P(x + 1, y + 1, c))
+                                   - (P(x - 1, y - 1, c) + 2.0f * P(x - 1, y, c)
+                                      + P(x - 1, y + 1, c));
+                        float gy = (P(x - 1, y + 1, c) + 2.0f * P(x, y + 1, c)
+                                    + P(x + 1, y + 1, c))
+                                   - (P(x - 1, y - 1, c) + 2.0f * P(x, y - 1, c)
+                                      + P(x + 1, y - 1, c));
+                        e += std::sqrt(gx * gx + gy * gy);
+                    }
+                    energy[size_t(y) * w + x] = e;
+                }
+            }
+        });
+
+        // Dynamic programming: cost of the cheapest seam ending at each
+        // pixel, coming from one of the three pixels above it.
+        for (int x = 0; x < w; ++x)
+            cost[x] = energy[x];
+        for (int y = 1; y < h; ++y) {
+            const float* above = &cost[size_t(y - 1) * w];
+            for (int x = 0; x < w; ++x) {
+                float m = above[x];
+                if (x > 0)
+                    m = std::min(m, above[x - 1]);
+                if (x < w - 1)
+                    m = std::min(m, above[x + 1]);
+                cost[size_t(y) * w + x] = energy[size_t(y) * w + x] + m;
+            }
+        }
+
+        // Trace the cheapest seam back up from the bottom row
+        const float* bottom = &cost[size_t(h - 1) * w];
+        seam[h - 1] = int(std::min_element(bottom, bottom + w) - bottom);
+        for (int y = h - 2; y >= 0; --y) {
+            const float* row = &cost[size_t(y) * w];
+            int xprev        = seam[y + 1];
+            int best         = xprev;
+            if (xprev > 0 && row[xprev - 1] < row[best])
+                best = xprev - 1;
+            if (xprev < w - 1 && row[xprev + 1] < row[best])
+                best = xprev + 1;
+            seam[y] = best;
+        }
+
+        // Remove the seam, compacting the rows to the new width
+        float* out = pixels.data();
+        for (int y = 0; y < h; ++y) {
+            const float* in = &pixels[size_t(y) * w * nc];
+            for (int x = 0; x < w; ++x) {
+                if (x == seam[y])
+                    continue;
+                for (int c = 0; c < nc; ++c)
+                    *out++ = in[x * nc + c];
+            }
+        }
+        --w;
+    }
+    pixels.resize(size_t(w) * h * nc);
+}
+
+
+
+// Transpose the w x h x nc float image in `pixels`.
+static void
+transpose_pixels(std::vector<float>& pixels, int w, int h, int nc)
+{
+    std::vector<float> t(pixels.size());
+    for (int y = 0; y < h; ++y)
+        for (int x = 0; x < w; ++x)
+            for (int c = 0; c < nc; ++c)
+                t[(size_t(x) * h + y) * nc + c]
+                    = pixels[(size_t(y) * w + x) * nc + c];
+    pixels.swap(t);
+}
+
+
+
+bool
+ImageBufAlgo::seam_carve(ImageBuf& dst, const ImageBuf& src, int width,
+                         int height, ROI roi, int nthreads)
+{
+    pvt::LoggedTimer logtime("IBA::seam_carve");
+    if (!src.initialized()) {
+        dst.errorfmt("Uninitialized input image");
+        return false;
+    }
+    if (src.deep()) {
+        dst.errorfmt("seam_carve does not support deep images");
+        return false;
+    }
+    if (!roi.defined())
+        roi = src.roi();
+    roi.chbegin = 0;
+    roi.chend   = src.nchannels();
+    if (roi.depth() != 1) {
+        dst.errorfmt("seam_carve does not support volume images");
+        return false;
+    }
+    if (width < 1 || height < 1 || width > roi.width()
+        || height > roi.height()) {
+        dst.errorfmt("seam_carve: can't carve {}x{} region to {}x{}",
+                     roi.width(), roi.height(), width, height);
+        return false;
+    }
+
+    int w  = roi.width();
+    int h  = roi.height();
+    int nc = roi.nchannels();
+    std::vector<float> pixels(roi.npixels() * nc);
+    if (!src.get_pixels(roi, TypeFloat, pixels.data())) {
+        dst.errorfmt("{}", src.geterror());
+        return false;
+    }
+    carve_columns(pixels, w, h, nc, w - width, nthreads);
+    if (height < h) {
+        transpose_pixels(pixels, w, h, nc);
+        carve_columns(pixels, h, w, nc, h - height, nthreads);
+        transpose_pixels(pixels, h, w, nc);
+    }
+
+    ROI dstroi(roi.xbegin, roi.xbegin + width, roi.ybegin, roi.ybegin + height,
+               roi.zbegin, roi.zend, 0, nc);
+    if (!dst.initialized()) {
+        ImageSpec spec = src.spec();
+        spec.set_roi(dstroi);
+        spec.set_roi_full(dstroi);
+        dst.reset(spec);
+    }
+    stride_t xstride = nc * sizeof(float);
+    stride_t ystride = xstride * width;
+    return dst.set_pixels(dstroi, TypeFloat, pixels.data(), xstride, ystride);
+}
+
+
+
+ImageBuf
+ImageBufAlgo::seam_carve(const ImageBuf& src, int width, int height, ROI roi,
+                         int nthreads)
+{
+    ImageBuf result;
+    bool ok = seam_carve(result, src, width, height, roi, nthreads);
+    if (!ok && !result.has_error())
+        result.errorfmt("ImageBufAlgo::seam_carve() error");
+    return result;
+}
+
+
+
 bool
 ImageBufAlgo::rotate(ImageBuf& dst, const ImageBuf& src, float angle,
                      float center_x, float center_y, string_view filtername,
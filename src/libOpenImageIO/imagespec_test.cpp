@@ -307,6 +307,123 @@ test_imagespec_from_ROI()
     OIIO_CHECK_EQUAL(spec.format, TypeFloat);
 }
 
+static void
+test_imagespec_with_size_format()
+{
+    std::cout << "test_imagespec_with_size_format\n";
+    ImageSpec spec(640, 480, 4, TypeUInt8);
+    spec.tile_width  = 64;
+    spec.tile_height = 64;
+    spec.channelformats.assign({ TypeUInt8, TypeUInt8, TypeUInt8, TypeHalf });
+    spec.attribute("foo", int(42));
+    spec.attribute("oiio:ColorSpace", "sRGB");
+
+    ImageSpec resized = spec.with_size(320, 240);
+    OIIO_CHECK_EQUAL(resized.width, 320);
+    OIIO_CHECK_EQUAL(resized.height, 240);
+    OIIO_CHECK_EQUAL(resized.full_width, 320);
+    OIIO_CHECK_EQUAL(resized.full_height, 240);
+    OIIO_CHECK_EQUAL(resized.nchannels, 4);
+    OIIO_CHECK_EQUAL(resized.tile_width, 64);
+    OIIO_CHECK_EQUAL(resized.alpha_channel, 3);
+    OIIO_CHECK_EQUAL(resized.channel_name(3), "A");
+    OIIO_CHECK_EQUAL(resized.channelformat(3), TypeHalf);
+    OIIO_CHECK_EQUAL(resized.get_int_attribute("foo"), 42);
+    OIIO_CHECK_EQUAL(resized.get_string_attribute("oiio:ColorSpace"), "sRGB");
+    // The original should be untouched
+    OIIO_CHECK_EQUAL(spec.width, 640);
+    OIIO_CHECK_EQUAL(spec.height, 480);
+
+    ImageSpec converted = spec.with_format(TypeFloat);
+    OIIO_CHECK_EQUAL(converted.format, TypeFloat);
+    OIIO_CHECK_ASSERT(converted.channelformats.empty());
+    OIIO_CHECK_EQUAL(converted.width, 640);
+    OIIO_CHECK_EQUAL(converted.get_int_attribute("foo"), 42);
+    OIIO_CHECK_EQUAL(spec.format, TypeUInt8);
+}
+
+
+
+static void
+test_imagespec_strip_attributes()
+{
+    std::cout << "test_imagespec_strip_attributes\n";
+    ImageSpec spec(64, 64, 3, TypeUInt8);
+    spec.attribute("farm:host", "node042");
+    spec.attribute("farm:user", "jdoe");
+    spec.attribute("Farm:jobid", 1234);
+    spec.attribute("Artist", "jdoe");
+    spec.attribute("oiio:ColorSpace", "sRGB");
+    spec.attribute("compression", "zip");
+
+    ImageSpec stripped  = spec;
+    std::string strip[] = { "farm:*", "Artist" };
+    stripped.strip_attributes(strip);
+    OIIO_CHECK_ASSERT(!stripped.find_attribute("farm:host"));
+    OIIO_CHECK_ASSERT(!stripped.find_attribute("farm:user"));
+    OIIO_CHECK_ASSERT(!stripped.find_attribute("Farm:jobid"));
+    OIIO_CHECK_ASSERT(!stripped.find_attribute("Artist"));
+    OIIO_CHECK_EQUAL(stripped.get_string_attribute("oiio:ColorSpace"), "sRGB");
+    OIIO_CHECK_EQUAL(stripped.get_string_attribute("compression"), "zip");
+    OIIO_CHECK_EQUAL(stripped.extra_attribs.size(), 2);
+
+    ImageSpec kept     = spec;
+    std::string keep[] = { "oiio:*", "compressio?" };
+    kept.keep_only_attributes(keep);
+    OIIO_CHECK_EQUAL(kept.extra_attribs.size(), 2);
+    OIIO_CHECK_EQUAL(kept.get_string_attribute("oiio:ColorSpace"), "sRGB");
+    OIIO_CHECK_EQUAL(kept.get_string_attribute("compression"), "zip");
+}
+
+
+
+static void
+test_imagespec_merge_channels()
+{
+    std::cout << "test_imagespec_merge_channels\n";
+    // Stack three RGB render AOVs into one 9-channel spec
+    ImageSpec diffuse(64, 64, 3, TypeHalf);
+    diffuse.attribute("oiio:subimagename", "diffuse");
+    ImageSpec specular(64, 64, 3, TypeHalf);
+    specular.attribute("oiio:subimagename", "specular");
+    ImageSpec emission(64, 64, 3, TypeFloat);
+    emission.attribute("oiio:subimagename", "emission");
+
+    ImageSpec merged          = diffuse;
+    const ImageSpec* others[] = { &specular, &emission };
+    merged.merge_channels_from(others);
+    OIIO_CHECK_EQUAL(merged.nchannels, 9);
+    std::vector<std::string> names = { "diffuse.R",  "diffuse.G",
+                                       "diffuse.B",  "specular.R",
+                                       "specular.G", "specular.B",
+                                       "emission.R", "emission.G",
+                                       "emission.B" };
+    OIIO_CHECK_ASSERT(merged.channelnames == names);
+    OIIO_CHECK_EQUAL(merged.format, TypeHalf);
+    OIIO_CHECK_EQUAL(merged.channelformat(0), TypeHalf);
+    OIIO_CHECK_EQUAL(merged.channelformat(5), TypeHalf);
+    OIIO_CHECK_EQUAL(merged.channelformat(8), TypeFloat);
+
+    // Unique names are left alone, and a collision with no subimage name
+    // to disambiguate still keeps the channel's own name.
+    ImageSpec depth(64, 64, 1, TypeFloat);
+    depth.channelnames = { "Z" };
+    depth.z_channel    = 0;
+    ImageSpec unnamed(64, 64, 1, TypeFloat);
+    unnamed.channelnames = { "Z" };
+    ImageSpec rgb(64, 64, 3, TypeFloat);
+    rgb.merge_channels_from(depth);
+    OIIO_CHECK_EQUAL(rgb.channel_name(3), "Z");
+    OIIO_CHECK_EQUAL(rgb.z_channel, 3);
+    OIIO_CHECK_ASSERT(rgb.channelformats.empty());
+    rgb.merge_channels_from(unnamed);
+    OIIO_CHECK_EQUAL(rgb.nchannels, 5);
+    OIIO_CHECK_EQUAL(rgb.channel_name(3), "subimage0.Z");
+    OIIO_CHECK_EQUAL(rgb.channel_name(4), "subimage1.Z");
+}
+
+
+
 static void
 test_imagespec_from_xml()
 {
@@ -336,6 +453,9 @@ main(int /*argc*/, char* /*argv*/[])
     test_imagespec_attribute_from_string();
     test_get_attribute();
     test_imagespec_from_ROI();
+    test_imagespec_with_size_format();
+    test_imagespec_strip_attributes();
+    test_imagespec_merge_channels();
     test_imagespec_from_xml();
 
     return unit_test_failures;
@@ -5,6 +5,7 @@
 
 #include <limits>
 #include <type_traits>
+#include <unordered_map>
 
 #include <OpenImageIO/Imath.h>
 
@@ -105,6 +106,31 @@ test_templates()
 
 
 
+static void
+test_hash()
+{
+    print("Testing hash\n");
+    std::hash<TypeDesc> h;
+    // Equal types hash equally, however they were constructed
+    OIIO_CHECK_EQUAL(h(TypeFloat), h(TypeDesc(TypeDesc::FLOAT)));
+    OIIO_CHECK_EQUAL(h(TypeColor), h(TypeDesc("color")));
+    OIIO_CHECK_EQUAL(h(TypeDesc(TypeDesc::INT, 2)), h(TypeDesc("int[2]")));
+    // Types differing in any one field are distinct keys
+    OIIO_CHECK_NE(h(TypeDesc(TypeDesc::FLOAT, 2)), h(TypeFloat));
+    OIIO_CHECK_NE(h(TypeDesc(TypeDesc::FLOAT, 2)),
+                  h(TypeDesc(TypeDesc::FLOAT, -1)));
+    std::unordered_map<TypeDesc, int> map;
+    const TypeDesc types[] = { TypeFloat,  TypeInt,    TypeColor,  TypePoint,
+                               TypeVector, TypeFloat4, TypeString, TypeMatrix };
+    for (int i = 0; i < int(std::size(types)); ++i)
+        map[types[i]] = i;
+    OIIO_CHECK_EQUAL(map.size(), std::size(types));
+    for (int i = 0; i < int(std::size(types)); ++i)
+        OIIO_CHECK_EQUAL(map[types[i]], i);
+}
+
+
+
 int
 main(int /*argc*/, char* /*argv*/[])
 {
@@ -113,6 +139,7 @@ main(int /*argc*/, char* /*argv*/[])
     OIIO_CHECK_EQUAL(sizeof(TypeDesc), sizeof(uint64_t));
 
     test_templates();
+    test_hash();
 
     test_type<float>("float", TypeDesc(TypeDesc::FLOAT), TypeFloat, 1.5f,
                      "1.5");
@@ -234,6 +234,30 @@ IBA_channels_ret(const ImageBuf& src, py::tuple channelorder,
 }
 
 
+bool
+IBA_channels_by_name(ImageBuf& dst, const ImageBuf& src,
+                     py::object channelnames_, bool fill_missing,
+                     float fillvalue, int nthreads)
+{
+    std::vector<std::string> channelnames;
+    py_to_stdvector(channelnames, channelnames_);
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::channels_by_name(dst, src, channelnames, fill_missing,
+                                          fillvalue, nthreads);
+}
+
+
+ImageBuf
+IBA_channels_by_name_ret(const ImageBuf& src, py::object channelnames,
+                         bool fill_missing, float fillvalue, int nthreads)
+{
+    ImageBuf result;
+    IBA_channels_by_name(result, src, channelnames, fill_missing, fillvalue,
+                         nthreads);
+    return result;
+}
+
+
 bool
 IBA_channel_append(ImageBuf& dst, const ImageBuf& A, const ImageBuf& B, ROI roi,
                    int nthreads)
@@ -273,19 +297,27 @@ IBA_deepen_ret(const ImageBuf& src, float zvalue, ROI roi, int nthreads)
 
 
 bool
-IBA_flatten(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads)
+IBA_flatten(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads,
+            float alpha_threshold, bool sort)
 {
     py::gil_scoped_release gil;
-    return ImageBufAlgo::flatten(dst, src, roi, nthreads);
+    return ImageBufAlgo::flatten(dst, src,
+                                 { { "alpha_threshold", alpha_threshold },
+                                   { "sort", int(sort) } },
+                                 roi, nthreads);
 }
 
 
 
 ImageBuf
-IBA_flatten_ret(const ImageBuf& src, ROI roi, int nthreads)
+IBA_flatten_ret(const ImageBuf& src, ROI roi, int nthreads,
+                float alpha_threshold, bool sort)
 {
     py::gil_scoped_release gil;
-    return ImageBufAlgo::flatten(src, roi, nthreads);
+    return ImageBufAlgo::flatten(src,
+                                 { { "alpha_threshold", alpha_threshold },
+                                   { "sort", int(sort) } },
+                                 roi, nthreads);
 }
 
 
@@ -471,6 +503,24 @@ IBA_flop_ret(const ImageBuf& src, ROI roi, int nthreads)
 }
 
 
+bool
+IBA_flip_axes(ImageBuf& dst, const ImageBuf& src, bool flip_x, bool flip_y,
+              bool flip_z, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::flip_axes(dst, src, flip_x, flip_y, flip_z, roi,
+                                   nthreads);
+}
+
+ImageBuf
+IBA_flip_axes_ret(const ImageBuf& src, bool flip_x, bool flip_y, bool flip_z,
+                  ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::flip_axes(src, flip_x, flip_y, flip_z, roi, nthreads);
+}
+
+
 bool
 IBA_reorient(ImageBuf& dst, const ImageBuf& src, int nthreads)
 {
@@ -1133,6 +1183,95 @@ IBA_rangecompress_ret(const ImageBuf& src, bool useluma = false,
 
 
 
+bool
+IBA_tonemap(ImageBuf& dst, const ImageBuf& src,
+            const std::string& method = "reinhard", float exposure = 0.0f,
+            float whitepoint = 0.0f, ROI roi = ROI::All(), int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::tonemap(dst, src, method, exposure, whitepoint, roi,
+                                 nthreads);
+}
+
+
+ImageBuf
+IBA_tonemap_ret(const ImageBuf& src, const std::string& method = "reinhard",
+                float exposure = 0.0f, float whitepoint = 0.0f,
+                ROI roi = ROI::All(), int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::tonemap(src, method, exposure, whitepoint, roi,
+                                 nthreads);
+}
+
+
+
+bool
+IBA_white_balance(ImageBuf& dst, const ImageBuf& src,
+                  float temperature = 6500.0f, float tint = 0.0f,
+                  ROI roi = ROI::All(), int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::white_balance(dst, src, temperature, tint, roi,
+                                       nthreads);
+}
+
+
+ImageBuf
+IBA_white_balance_ret(const ImageBuf& src, float temperature = 6500.0f,
+                      float tint = 0.0f, ROI roi = ROI::All(), int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::white_balance(src, temperature, tint, roi, nthreads);
+}
+
+
+
+bool
+IBA_vignette(ImageBuf& dst, const ImageBuf& src, float amount = 0.5f,
+             float radius = 0.5f, float roundness = 1.0f, ROI roi = ROI::All(),
+             int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::vignette(dst, src, amount, radius, roundness, roi,
+                                  nthreads);
+}
+
+
+ImageBuf
+IBA_vignette_ret(const ImageBuf& src, float amount = 0.5f, float radius = 0.5f,
+                 float roundness = 1.0f, ROI roi = ROI::All(), int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::vignette(src, amount, radius, roundness, roi,
+                                  nthreads);
+}
+
+
+
+bool
+IBA_film_grain(ImageBuf& dst, const ImageBuf& src, float intensity = 0.05f,
+               float size = 1.5f, int seed = 0, bool mono = true,
+               ROI roi = ROI::All(), int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::film_grain(dst, src, intensity, size, seed, mono, roi,
+                                    nthreads);
+}
+
+
+ImageBuf
+IBA_film_grain_ret(const ImageBuf& src, float intensity = 0.05f,
+                   float size = 1.5f, int seed = 0, bool mono = true,
+                   ROI roi = ROI::All(), int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::film_grain(src, intensity, size, seed, mono, roi,
+                                    nthreads);
+}
+
+
+
 bool
 IBA_premult(ImageBuf& dst, const ImageBuf& src, ROI roi = ROI::All(),
             int nthreads = 0)
@@ -1245,6 +1384,29 @@ IBA_contrast_remap_ret(const ImageBuf& src, py::object black_,
 
 
 
+bool
+IBA_curve_remap(ImageBuf& dst, const ImageBuf& src, py::object points_,
+                int channel = -1, ROI roi = ROI::All(), int nthreads = 0)
+{
+    std::vector<float> points;
+    py_to_stdvector(points, points_);
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::curve_remap(dst, src, points, channel, roi,
+                                     nthreads);
+}
+
+
+ImageBuf
+IBA_curve_remap_ret(const ImageBuf& src, py::object points_, int channel = -1,
+                    ROI roi = ROI::All(), int nthreads = 0)
+{
+    ImageBuf dst;
+    IBA_curve_remap(dst, src, points_, channel, roi, nthreads);
+    return dst;
+}
+
+
+
 ImageBufAlgo::PixelStats
 IBA_computePixelStats_ret(const ImageBuf& src, ROI roi, int nthreads)
 {
@@ -1295,6 +1457,18 @@ IBA_compare(const ImageBuf& A, const ImageBuf& B, float failthresh,
 
 
 
+bool
+IBA_content_equals(const ImageBuf& A, const ImageBuf& B,
+                   bool compare_metadata = false, float tolerance = 0.0f,
+                   int nthreads = 0)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::content_equals(A, B, compare_metadata, tolerance,
+                                        nthreads);
+}
+
+
+
 bool
 IBA_compare_Yee(const ImageBuf& A, const ImageBuf& B,
                 ImageBufAlgo::CompareResults& result, float luminance,
@@ -1436,6 +1610,23 @@ IBA_resample_ret(const ImageBuf& src, bool interpolate, ROI roi, int nthreads)
 }
 
 
+bool
+IBA_seam_carve(ImageBuf& dst, const ImageBuf& src, int width, int height,
+               ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::seam_carve(dst, src, width, height, roi, nthreads);
+}
+
+ImageBuf
+IBA_seam_carve_ret(const ImageBuf& src, int width, int height, ROI roi,
+                   int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::seam_carve(src, width, height, roi, nthreads);
+}
+
+
 bool
 IBA_st_warp(ImageBuf& dst, const ImageBuf& src, const ImageBuf& stbuf,
             const std::string& filtername = "", float filterwidth = 0.0f,
@@ -1506,6 +1697,27 @@ IBA_fit_ret_old(const ImageBuf& src, const std::string& filtername = "",
 
 
 
+bool
+IBA_upscale(ImageBuf& dst, const ImageBuf& src, float scale,
+            const std::string& filtername, bool deringing, ROI roi,
+            int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::upscale(dst, src, scale, filtername, deringing, roi,
+                                 nthreads);
+}
+
+ImageBuf
+IBA_upscale_ret(const ImageBuf& src, float scale, const std::string& filtername,
+                bool deringing, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::upscale(src, scale, filtername, deringing, roi,
+                                 nthreads);
+}
+
+
+
 bool
 IBA_make_kernel(ImageBuf& dst, const std::string& name, float width,
                 float height, float depth, bool normalize)
@@ -1565,6 +1777,26 @@ IBA_unsharp_mask_ret(const ImageBuf& src, const std::string& kernel,
 
 
 
+bool
+IBA_clarity(ImageBuf& dst, const ImageBuf& src, float amount, float radius,
+            float sigma_range, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::clarity(dst, src, amount, radius, sigma_range, roi,
+                                 nthreads);
+}
+
+ImageBuf
+IBA_clarity_ret(const ImageBuf& src, float amount, float radius,
+                float sigma_range, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::clarity(src, amount, radius, sigma_range, roi,
+                                 nthreads);
+}
+
+
+
 bool
 IBA_median_filter(ImageBuf& dst, const ImageBuf& src, int width, int height,
                   ROI roi, int nthreads)
@@ -1583,6 +1815,49 @@ IBA_median_filter_ret(const ImageBuf& src, int width, int height, ROI roi,
 
 
 
+bool
+IBA_bilateral_filter(ImageBuf& dst, const ImageBuf& src, float sigma_spatial,
+                     float sigma_range, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::bilateral_filter(dst, src, sigma_spatial, sigma_range,
+                                          roi, nthreads);
+}
+
+ImageBuf
+IBA_bilateral_filter_ret(const ImageBuf& src, float sigma_spatial,
+                         float sigma_range, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::bilateral_filter(src, sigma_spatial, sigma_range, roi,
+                                          nthreads);
+}
+
+
+
+bool
+IBA_joint_bilateral_upsample(ImageBuf& dst, const ImageBuf& src,
+                             const ImageBuf& guide, float sigma_spatial,
+                             float sigma_range, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::joint_bilateral_upsample(dst, src, guide,
+                                                  sigma_spatial, sigma_range,
+                                                  roi, nthreads);
+}
+
+ImageBuf
+IBA_joint_bilateral_upsample_ret(const ImageBuf& src, const ImageBuf& guide,
+                                 float sigma_spatial, float sigma_range,
+                                 ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::joint_bilateral_upsample(src, guide, sigma_spatial,
+                                                  sigma_range, roi, nthreads);
+}
+
+
+
 bool
 IBA_dilate(ImageBuf& dst, const ImageBuf& src, int width, int height, ROI roi,
            int nthreads)
@@ -1633,6 +1908,31 @@ IBA_laplacian_ret(const ImageBuf& src, ROI roi, int nthreads)
 }
 
 
+bool
+IBA_sobel(ImageBuf& dst_x, ImageBuf& dst_y, const ImageBuf& src, ROI roi,
+          int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::sobel(dst_x, dst_y, src, roi, nthreads);
+}
+
+
+bool
+IBA_gradient_magnitude(ImageBuf& dst, const ImageBuf& src, ROI roi,
+                       int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::gradient_magnitude(dst, src, roi, nthreads);
+}
+
+ImageBuf
+IBA_gradient_magnitude_ret(const ImageBuf& src, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::gradient_magnitude(src, roi, nthreads);
+}
+
+
 
 bool
 IBA_fft(ImageBuf& dst, const ImageBuf& src, ROI roi, int nthreads)
@@ -1737,6 +2037,65 @@ IBA_fillholes_pushpull_ret(const ImageBuf& src, ROI roi, int nthreads)
 
 
 
+bool
+IBA_poisson_clone(ImageBuf& dst, const ImageBuf& background,
+                  const ImageBuf& src, const ImageBuf& mask, int xoffset,
+                  int yoffset, int iterations, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::poisson_clone(dst, background, src, mask, xoffset,
+                                       yoffset, iterations, roi, nthreads);
+}
+
+ImageBuf
+IBA_poisson_clone_ret(const ImageBuf& background, const ImageBuf& src,
+                      const ImageBuf& mask, int xoffset, int yoffset,
+                      int iterations, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::poisson_clone(background, src, mask, xoffset, yoffset,
+                                       iterations, roi, nthreads);
+}
+
+
+
+// Copy a Python list or tuple of ImageBufs for exposure_fusion
+static std::vector<ImageBuf>
+py_to_imagebufs(py::object images_)
+{
+    std::vector<ImageBuf> images;
+    for (auto item : images_)
+        images.push_back(item.cast<const ImageBuf&>());
+    return images;
+}
+
+
+bool
+IBA_exposure_fusion(ImageBuf& dst, py::object images_, float contrast_weight,
+                    float saturation_weight, float exposure_weight, ROI roi,
+                    int nthreads)
+{
+    std::vector<ImageBuf> images = py_to_imagebufs(images_);
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::exposure_fusion(dst, images, contrast_weight,
+                                         saturation_weight, exposure_weight,
+                                         roi, nthreads);
+}
+
+ImageBuf
+IBA_exposure_fusion_ret(py::object images_, float contrast_weight,
+                        float saturation_weight, float exposure_weight,
+                        ROI roi, int nthreads)
+{
+    std::vector<ImageBuf> images = py_to_imagebufs(images_);
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::exposure_fusion(images, contrast_weight,
+                                         saturation_weight, exposure_weight,
+                                         roi, nthreads);
+}
+
+
+
 bool
 IBA_over(ImageBuf& dst, const ImageBuf& A, const ImageBuf& B,
          ROI roi = ROI::All(), int nthreads = 0)
@@ -1773,6 +2132,31 @@ IBA_zover_ret(const ImageBuf& A, const ImageBuf& B, bool z_zeroisinf = false,
 
 
 
+bool
+IBA_chroma_key(ImageBuf& dst, const ImageBuf& src, py::object keycolor_,
+               float tolerance = 0.1f, float softness = 0.1f,
+               bool despill = true, ROI roi = ROI::All(), int nthreads = 0)
+{
+    std::vector<float> keycolor;
+    py_to_stdvector(keycolor, keycolor_);
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::chroma_key(dst, src, keycolor, tolerance, softness,
+                                    despill, roi, nthreads);
+}
+
+ImageBuf
+IBA_chroma_key_ret(const ImageBuf& src, py::object keycolor_,
+                   float tolerance = 0.1f, float softness = 0.1f,
+                   bool despill = true, ROI roi = ROI::All(), int nthreads = 0)
+{
+    ImageBuf dst;
+    IBA_chroma_key(dst, src, keycolor_, tolerance, softness, despill, roi,
+                   nthreads);
+    return dst;
+}
+
+
+
 bool
 IBA_colorconvert(ImageBuf& dst, const ImageBuf& src, const std::string& from,
                  const std::string& to, bool unpremult = true,
@@ -2156,6 +2540,37 @@ IBA_nonzero_region(const ImageBuf& src, ROI roi, int nthreads)
 
 
 
+ImageBuf
+IBA_connected_components_ret(const ImageBuf& src, int channel, float threshold,
+                             int connectivity, ROI roi, int nthreads)
+{
+    py::gil_scoped_release gil;
+    return ImageBufAlgo::connected_components(src, channel, threshold,
+                                              connectivity, roi, nthreads);
+}
+
+
+
+py::object
+IBA_estimate_translation(const ImageBuf& reference, const ImageBuf& target,
+                         int max_shift = 16, ROI roi = ROI::All(),
+                         int nthreads = 0)
+{
+    float dx = 0.0f, dy = 0.0f;
+    bool ok;
+    {
+        py::gil_scoped_release gil;
+        ok = ImageBufAlgo::estimate_translation(reference, target, dx, dy,
+                                                max_shift, roi, nthreads);
+    }
+    if (ok)
+        return py::make_tuple(dx, dy);
+    else
+        return py::none();
+}
+
+
+
 py::object
 IBA_color_range_check(ImageBuf& src, const py::object& low,
                       const py::object& high, ROI roi, int nthreads)
@@ -2305,6 +2720,41 @@ IBA_histogram(const ImageBuf& src, int channel = 0, int bins = 256,
 
 
 
+py::object
+IBA_percentiles(const ImageBuf& src, py::object percentiles_tuple,
+                ROI roi = {}, int nthreads = 0)
+{
+    std::vector<float> pcts;
+    py_to_stdvector(pcts, percentiles_tuple);
+    std::vector<std::vector<float>> result;
+    {
+        py::gil_scoped_release gil;
+        result = ImageBufAlgo::percentiles(src, pcts, roi, nthreads);
+    }
+    py::tuple channels(result.size());
+    for (size_t c = 0; c < result.size(); ++c)
+        channels[c] = C_to_tuple<float>(result[c]);
+    return channels;
+}
+
+
+
+py::object
+IBA_palette(const ImageBuf& src, int k, ROI roi = {}, int nthreads = 0)
+{
+    std::vector<std::vector<float>> result;
+    {
+        py::gil_scoped_release gil;
+        result = ImageBufAlgo::palette(src, k, roi, nthreads);
+    }
+    py::tuple colors(result.size());
+    for (size_t i = 0; i < result.size(); ++i)
+        colors[i] = C_to_tuple<float>(result[i]);
+    return colors;
+}
+
+
+
 bool
 IBA_capture_image(ImageBuf& dst, int cameranum,
                   TypeDesc::BASETYPE convert = TypeDesc::UNKNOWN)
@@ -2438,6 +2888,13 @@ declare_imagebufalgo(py::module& m)
                     "newchannelnames"_a       = py::tuple(),
                     "shuffle_channel_names"_a = false, "nthreads"_a = 0)
 
+        .def_static("channels_by_name", &IBA_channels_by_name, "dst"_a,
+                    "src"_a, "channelnames"_a, "fill_missing"_a = false,
+                    "fillvalue"_a = 0.0f, "nthreads"_a = 0)
+        .def_static("channels_by_name", &IBA_channels_by_name_ret, "src"_a,
+                    "channelnames"_a, "fill_missing"_a = false,
+                    "fillvalue"_a = 0.0f, "nthreads"_a = 0)
+
         .def_static("channel_append", IBA_channel_append, "dst"_a, "A"_a, "B"_a,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
         .def_static("channel_append", IBA_channel_append_ret, "A"_a, "B"_a,
@@ -2449,9 +2906,11 @@ declare_imagebufalgo(py::module& m)
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
 
         .def_static("flatten", IBA_flatten, "dst"_a, "src"_a,
-                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+                    "roi"_a = ROI::All(), "nthreads"_a = 0,
+                    "alpha_threshold"_a = 1.0f, "sort"_a = false)
         .def_static("flatten", IBA_flatten_ret, "src"_a, "roi"_a = ROI::All(),
-                    "nthreads"_a = 0)
+                    "nthreads"_a = 0, "alpha_threshold"_a = 1.0f,
+                    "sort"_a = false)
 
         .def_static("deep_merge", IBA_deep_merge, "dst"_a, "A"_a, "B"_a,
                     "occlusion_cull"_a = true, "roi"_a = ROI::All(),
@@ -2510,6 +2969,13 @@ declare_imagebufalgo(py::module& m)
         .def_static("flop", IBA_flop_ret, "src"_a, "roi"_a = ROI::All(),
                     "nthreads"_a = 0)
 
+        .def_static("flip_axes", IBA_flip_axes, "dst"_a, "src"_a, "flip_x"_a,
+                    "flip_y"_a, "flip_z"_a = false, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("flip_axes", IBA_flip_axes_ret, "src"_a, "flip_x"_a,
+                    "flip_y"_a, "flip_z"_a = false, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+
         .def_static("reorient", IBA_reorient, "dst"_a, "src"_a,
                     "nthreads"_a = 0)
         .def_static("reorient", IBA_reorient_ret, "src"_a, "nthreads"_a = 0)
@@ -2635,6 +3101,36 @@ declare_imagebufalgo(py::module& m)
         .def_static("rangeexpand", &IBA_rangeexpand_ret, "src"_a,
                     "useluma"_a = false, "roi"_a = ROI::All(), "nthreads"_a = 0)
 
+        .def_static("tonemap", &IBA_tonemap, "dst"_a, "src"_a,
+                    "method"_a = "reinhard", "exposure"_a = 0.0f,
+                    "whitepoint"_a = 0.0f, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("tonemap", &IBA_tonemap_ret, "src"_a,
+                    "method"_a = "reinhard", "exposure"_a = 0.0f,
+                    "whitepoint"_a = 0.0f, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+
+        .def_static("white_balance", &IBA_white_balance, "dst"_a, "src"_a,
+                    "temperature"_a = 6500.0f, "tint"_a = 0.0f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("white_balance", &IBA_white_balance_ret, "src"_a,
+                    "temperature"_a = 6500.0f, "tint"_a = 0.0f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+
+        .def_static("vignette", &IBA_vignette, "dst"_a, "src"_a,
+                    "amount"_a = 0.5f, "radius"_a = 0.5f, "roundness"_a = 1.0f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("vignette", &IBA_vignette_ret, "src"_a, "amount"_a = 0.5f,
+                    "radius"_a = 0.5f, "roundness"_a = 1.0f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+
+        .def_static("film_grain", &IBA_film_grain, "dst"_a, "src"_a,
+                    "intensity"_a = 0.05f, "size"_a = 1.5f, "seed"_a = 0,
+                    "mono"_a = true, "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("film_grain", &IBA_film_grain_ret, "src"_a,
+                    "intensity"_a = 0.05f, "size"_a = 1.5f, "seed"_a = 0,
+                    "mono"_a = true, "roi"_a = ROI::All(), "nthreads"_a = 0)
+
         .def_static("premult", &IBA_premult, "dst"_a, "src"_a,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
         .def_static("premult", &IBA_premult_ret, "src"_a, "roi"_a = ROI::All(),
@@ -2694,6 +3190,12 @@ declare_imagebufalgo(py::module& m)
                     "max"_a = 1.0f, "scontrast"_a = 1.0f, "sthresh"_a = 0.5f,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
 
+        .def_static("curve_remap", &IBA_curve_remap, "dst"_a, "src"_a,
+                    "points"_a, "channel"_a = -1, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("curve_remap", &IBA_curve_remap_ret, "src"_a, "points"_a,
+                    "channel"_a = -1, "roi"_a = ROI::All(), "nthreads"_a = 0)
+
         .def_static("saturate", &IBA_saturate, "dst"_a, "src"_a,
                     "scale"_a = 0.0f, "firstchannel"_a = 0,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
@@ -2820,6 +3322,10 @@ declare_imagebufalgo(py::module& m)
                     "failthresh"_a, "warnthresh"_a, "roi"_a = ROI::All(),
                     "nthreads"_a = 0)
 
+        .def_static("content_equals", &IBA_content_equals, "A"_a, "B"_a,
+                    "compare_metadata"_a = false, "tolerance"_a = 0.0f,
+                    "nthreads"_a = 0)
+
         .def_static("compare_Yee", &IBA_compare_Yee, "A"_a, "B"_a, "result"_a,
                     "luminance"_a = 100, "fov"_a = 45, "roi"_a = ROI::All(),
                     "nthreads"_a = 0)
@@ -2844,6 +3350,14 @@ declare_imagebufalgo(py::module& m)
         .def_static("nonzero_region", &IBA_nonzero_region, "src"_a,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
 
+        .def_static("connected_components", &IBA_connected_components_ret,
+                    "src"_a, "channel"_a = 0, "threshold"_a = 0.5f,
+                    "connectivity"_a = 8, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("estimate_translation", &IBA_estimate_translation,
+                    "reference"_a, "target"_a, "max_shift"_a = 16,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+
         .def_static("computePixelHashSHA1", &IBA_computePixelHashSHA1, "src"_a,
                     "extrainfo"_a = "", "roi"_a = ROI::All(), "blocksize"_a = 0,
                     "nthreads"_a = 0)
@@ -2888,6 +3402,12 @@ declare_imagebufalgo(py::module& m)
                     "interpolate"_a = true, "roi"_a = ROI::All(),
                     "nthreads"_a = 0)
 
+        .def_static("seam_carve", &IBA_seam_carve, "dst"_a, "src"_a,
+                    "width"_a, "height"_a, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("seam_carve", &IBA_seam_carve_ret, "src"_a, "width"_a,
+                    "height"_a, "roi"_a = ROI::All(), "nthreads"_a = 0)
+
         .def_static("st_warp", &IBA_st_warp, "dst"_a, "src"_a, "stbuf"_a,
                     "filtername"_a = "", "filterwidth"_a = 0.0f, "chan_s"_a = 0,
                     "chan_t"_a = 1, "flip_s"_a = false, "flip_t"_a = false,
@@ -2911,6 +3431,13 @@ declare_imagebufalgo(py::module& m)
                     "filterwidth"_a = 0.0f, "exact"_a = false,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
 
+        .def_static("upscale", &IBA_upscale, "dst"_a, "src"_a, "scale"_a,
+                    "filtername"_a = "lanczos3", "deringing"_a = true,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("upscale", &IBA_upscale_ret, "src"_a, "scale"_a,
+                    "filtername"_a = "lanczos3", "deringing"_a = true,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+
         .def_static("make_kernel", &IBA_make_kernel, "dst"_a, "name"_a,
                     "width"_a, "height"_a, "depth"_a = 1.0f,
                     "normalize"_a = true)
@@ -2930,6 +3457,13 @@ declare_imagebufalgo(py::module& m)
                     "kernel"_a = "gaussian", "width"_a = 3.0f,
                     "contrast"_a = 1.0f, "threshold"_a = 0.0f,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("clarity", &IBA_clarity, "dst"_a, "src"_a,
+                    "amount"_a = 0.5f, "radius"_a = 5.0f,
+                    "sigma_range"_a = 0.1f, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("clarity", &IBA_clarity_ret, "src"_a, "amount"_a = 0.5f,
+                    "radius"_a = 5.0f, "sigma_range"_a = 0.1f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
 
         .def_static("median_filter", &IBA_median_filter, "dst"_a, "src"_a,
                     "width"_a = 3, "height"_a = -1, "roi"_a = ROI::All(),
@@ -2938,6 +3472,22 @@ declare_imagebufalgo(py::module& m)
                     "width"_a = 3, "height"_a = -1, "roi"_a = ROI::All(),
                     "nthreads"_a = 0)
 
+        .def_static("bilateral_filter", &IBA_bilateral_filter, "dst"_a,
+                    "src"_a, "sigma_spatial"_a = 1.0f, "sigma_range"_a = 0.1f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("bilateral_filter", &IBA_bilateral_filter_ret, "src"_a,
+                    "sigma_spatial"_a = 1.0f, "sigma_range"_a = 0.1f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("joint_bilateral_upsample",
+                    &IBA_joint_bilateral_upsample, "dst"_a, "src"_a,
+                    "guide"_a, "sigma_spatial"_a = 1.0f,
+                    "sigma_range"_a = 0.1f, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("joint_bilateral_upsample",
+                    &IBA_joint_bilateral_upsample_ret, "src"_a, "guide"_a,
+                    "sigma_spatial"_a = 1.0f, "sigma_range"_a = 0.1f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+
         .def_static("dilate", &IBA_dilate, "dst"_a, "src"_a, "width"_a = 3,
                     "height"_a = -1, "roi"_a = ROI::All(), "nthreads"_a = 0)
         .def_static("dilate", &IBA_dilate_ret, "src"_a, "width"_a = 3,
@@ -2953,6 +3503,14 @@ declare_imagebufalgo(py::module& m)
         .def_static("laplacian", &IBA_laplacian_ret, "src"_a,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
 
+        .def_static("sobel", &IBA_sobel, "dst_x"_a, "dst_y"_a, "src"_a,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+
+        .def_static("gradient_magnitude", &IBA_gradient_magnitude, "dst"_a,
+                    "src"_a, "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("gradient_magnitude", &IBA_gradient_magnitude_ret, "src"_a,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+
         .def_static("fft", &IBA_fft, "dst"_a, "src"_a, "roi"_a = ROI::All(),
                     "nthreads"_a = 0)
         .def_static("fft", &IBA_fft_ret, "src"_a, "roi"_a = ROI::All(),
@@ -2991,6 +3549,22 @@ declare_imagebufalgo(py::module& m)
                     "src"_a, "roi"_a = ROI::All(), "nthreads"_a = 0)
         .def_static("fillholes_pushpull", &IBA_fillholes_pushpull_ret, "src"_a,
                     "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("poisson_clone", &IBA_poisson_clone, "dst"_a,
+                    "background"_a, "src"_a, "mask"_a, "xoffset"_a = 0,
+                    "yoffset"_a = 0, "iterations"_a = 500, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("poisson_clone", &IBA_poisson_clone_ret, "background"_a,
+                    "src"_a, "mask"_a, "xoffset"_a = 0, "yoffset"_a = 0,
+                    "iterations"_a = 500, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
+        .def_static("exposure_fusion", &IBA_exposure_fusion, "dst"_a,
+                    "images"_a, "contrast_weight"_a = 1.0f,
+                    "saturation_weight"_a = 1.0f, "exposure_weight"_a = 1.0f,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("exposure_fusion", &IBA_exposure_fusion_ret, "images"_a,
+                    "contrast_weight"_a = 1.0f, "saturation_weight"_a = 1.0f,
+                    "exposure_weight"_a = 1.0f, "roi"_a = ROI::All(),
+                    "nthreads"_a = 0)
 
         .def_static("capture_image", &IBA_capture_image, "dst"_a,
                     "cameranum"_a = 0, "convert"_a = TypeDesc::UNKNOWN)
@@ -3008,6 +3582,12 @@ declare_imagebufalgo(py::module& m)
         .def_static("zover", &IBA_zover_ret, "A"_a, "B"_a,
                     "z_zeroisinf"_a = false, "roi"_a = ROI::All(),
                     "nthreads"_a = 0)
+        .def_static("chroma_key", &IBA_chroma_key, "dst"_a, "src"_a,
+                    "keycolor"_a, "tolerance"_a = 0.1f, "softness"_a = 0.1f,
+                    "despill"_a = true, "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("chroma_key", &IBA_chroma_key_ret, "src"_a, "keycolor"_a,
+                    "tolerance"_a = 0.1f, "softness"_a = 0.1f,
+                    "despill"_a = true, "roi"_a = ROI::All(), "nthreads"_a = 0)
 
         .def_static("render_point", &IBA_render_point, "dst"_a, "x"_a, "y"_a,
                     "color"_a = py::none())
@@ -3032,6 +3612,10 @@ declare_imagebufalgo(py::module& m)
                     "bins"_a = 256, "min"_a = 0.0f, "max"_a = 1.0f,
                     "ignore_empty"_a = false, "roi"_a = ROI::All(),
                     "nthreads"_a = 0)
+        .def_static("percentiles", &IBA_percentiles, "src"_a,
+                    "percentiles"_a, "roi"_a = ROI::All(), "nthreads"_a = 0)
+        .def_static("palette", &IBA_palette, "src"_a, "k"_a,
+                    "roi"_a = ROI::All(), "nthreads"_a = 0)
 
         .def_static("make_texture", &IBA_make_texture_filename, "mode"_a,
                     "filename"_a, "outputfilename"_a, "config"_a = ImageSpec())
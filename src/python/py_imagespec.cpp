@@ -231,6 +231,22 @@ declare_imagespec(py::module& m)
                 return spec.erase_attribute(name, type, casesensitive);
             },
             "name"_a = "", "type"_a = TypeUnknown, "casesensitive"_a = false)
+        .def(
+            "strip_attributes",
+            [](ImageSpec& spec, py::object patterns_) {
+                std::vector<std::string> patterns;
+                py_to_stdvector(patterns, patterns_);
+                spec.strip_attributes(patterns);
+            },
+            "patterns"_a)
+        .def(
+            "keep_only_attributes",
+            [](ImageSpec& spec, py::object patterns_) {
+                std::vector<std::string> patterns;
+                py_to_stdvector(patterns, patterns_);
+                spec.keep_only_attributes(patterns);
+            },
+            "patterns"_a)
 
         .def_static(
             "metadata_val",
@@ -261,6 +277,26 @@ declare_imagespec(py::module& m)
         .def("valid_tile_range", &ImageSpec::valid_tile_range, "xbegin"_a,
              "xend"_a, "ybegin"_a, "yend"_a, "zbegin"_a, "zend"_a)
         .def("copy_dimensions", &ImageSpec::copy_dimensions, "other"_a)
+        .def("with_size", &ImageSpec::with_size, "width"_a, "height"_a)
+        .def("with_format",
+             [](const ImageSpec& self, TypeDesc t) {
+                 return self.with_format(t);
+             })
+        .def(
+            "merge_channels_from",
+            [](ImageSpec& self, const ImageSpec& other) {
+                self.merge_channels_from(other);
+            },
+            "other"_a)
+        .def(
+            "merge_channels_from",
+            [](ImageSpec& self, const std::vector<ImageSpec>& others) {
+                std::vector<const ImageSpec*> ptrs;
+                for (auto& other : others)
+                    ptrs.push_back(&other);
+                self.merge_channels_from(ptrs);
+            },
+            "others"_a)
         .def(
             "set_colorspace",
             [](ImageSpec& self, const std::string& cs) {